@@ -0,0 +1,112 @@
+// © 2024 Max Karou. All Rights Reserved.
+// Licensed under Apache Version 2.0, or MIT License, at your discretion.
+//
+// Apache License: http://www.apache.org/licenses/LICENSE-2.0
+// MIT License: http://opensource.org/licenses/MIT
+//
+// Usage of this file is permitted solely under a sanctioned license.
+
+//! Compares the allocations of `Codec::encode` against `Codec::encode_into`
+//! with a reused buffer, for a contract-call transaction with 50 arguments.
+//! Also compares `Codec::len` against `encode().len()` for the elements of a
+//! 1000 element `UInt` list, & decoding that list.
+//!
+//! Run with `cargo bench -p stacks-rs --bench encode`.
+
+mod common;
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use stacks_rs::clarity::decode_clarity_type;
+use stacks_rs::clarity::Clarity;
+use stacks_rs::clarity::Codec;
+use stacks_rs::clarity::FnArguments;
+use stacks_rs::clarity::List;
+use stacks_rs::clarity::UInt;
+use stacks_rs::transaction::STXContractCall;
+use stacks_rs::transaction::StacksMainnet;
+use stacks_rs::transaction::Transaction;
+use stacks_rs::SecretKey;
+
+const ITERATIONS: usize = 100_000;
+const ARGUMENTS: u128 = 50;
+const LIST_ITERATIONS: usize = 1_000;
+const LIST_ELEMENTS: u128 = 1_000;
+
+fn bench<F>(name: &str, iterations: usize, mut f: F)
+where
+    F: FnMut(),
+{
+    let before = common::allocations();
+    let start = Instant::now();
+
+    for _ in 0..iterations {
+        f();
+    }
+
+    let elapsed = start.elapsed();
+    let allocations = (common::allocations() - before) / iterations;
+    println!("{name:<20} {elapsed:>12?} {allocations:>10} allocations/iter");
+}
+
+fn transaction() -> Transaction {
+    let args = (0..ARGUMENTS)
+        .map(|i| Box::new(UInt::new(i)) as Box<dyn Clarity>)
+        .collect::<Vec<_>>();
+
+    STXContractCall::builder()
+        .address("SP3FGQ8Z7JY9BWYZ5WM53E0M9NK7WHJF0691NZ159")
+        .contract("example")
+        .fn_name("batch")
+        .fn_args(FnArguments::new(args))
+        .sender(SecretKey::from_slice(&[0x01; 32]).unwrap())
+        .network(StacksMainnet::new())
+        .build()
+        .transaction()
+        .unwrap()
+}
+
+fn list() -> List {
+    let values = (0..LIST_ELEMENTS)
+        .map(|i| Box::new(UInt::new(i)) as Box<dyn Clarity>)
+        .collect::<Vec<_>>();
+
+    List::new(values)
+}
+
+fn main() {
+    let tx = transaction();
+
+    bench("encode", ITERATIONS, || {
+        black_box(black_box(&tx).encode().unwrap());
+    });
+
+    let mut out = Vec::with_capacity(tx.encode().unwrap().len());
+    bench("encode_into", ITERATIONS, || {
+        out.clear();
+        black_box(&tx).encode_into(&mut out).unwrap();
+        black_box(&out);
+    });
+
+    assert_eq!(out, tx.encode().unwrap());
+
+    let list = list();
+    let encoded = list.encode().unwrap();
+
+    bench("list_encode_len", LIST_ITERATIONS, || {
+        for value in black_box(&list).value() {
+            black_box(value.encode().unwrap().len());
+        }
+    });
+
+    bench("list_len", LIST_ITERATIONS, || {
+        for value in black_box(&list).value() {
+            black_box(value.len().unwrap());
+        }
+    });
+
+    bench("list_decode", LIST_ITERATIONS, || {
+        black_box(decode_clarity_type(black_box(&encoded)).unwrap());
+    });
+}
@@ -0,0 +1,132 @@
+// © 2024 Max Karou. All Rights Reserved.
+// Licensed under Apache Version 2.0, or MIT License, at your discretion.
+//
+// Apache License: http://www.apache.org/licenses/LICENSE-2.0
+// MIT License: http://opensource.org/licenses/MIT
+//
+// Usage of this file is permitted solely under a sanctioned license.
+
+//! Compares the hex encoding of 20-byte arrays (e.g. `Hash160`), and the
+//! throughput of encoding & decoding a 64 KB buffer against a per-nibble
+//! reference implementation.
+//!
+//! Run with `cargo bench -p stacks-rs --bench hex`.
+
+mod common;
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use stacks_rs::crypto::bytes_to_hex;
+use stacks_rs::crypto::bytes_to_hex_fixed;
+use stacks_rs::crypto::bytes_to_hex_into;
+use stacks_rs::crypto::hex_to_bytes;
+
+const ITERATIONS: usize = 1_000_000;
+
+fn bench<F>(name: &str, mut f: F)
+where
+    F: FnMut(&[u8; 20]),
+{
+    let input = [0xab; 20];
+    let before = common::allocations();
+    let start = Instant::now();
+
+    for _ in 0..ITERATIONS {
+        f(black_box(&input));
+    }
+
+    let elapsed = start.elapsed();
+    let allocations = common::allocations() - before;
+    println!("{name:<20} {elapsed:>12?} {allocations:>10} allocations");
+}
+
+const LARGE_SIZE: usize = 64 * 1024;
+const LARGE_ITERATIONS: usize = 200;
+
+fn bench_large<F>(name: &str, mut f: F)
+where
+    F: FnMut(),
+{
+    let start = Instant::now();
+
+    for _ in 0..LARGE_ITERATIONS {
+        f();
+    }
+
+    let elapsed = start.elapsed();
+    #[allow(clippy::cast_precision_loss)]
+    let throughput = (LARGE_SIZE * LARGE_ITERATIONS) as f64 / elapsed.as_secs_f64() / 1e6;
+    println!("{name:<20} {elapsed:>12?} {throughput:>10.1} MB/s");
+}
+
+/// The per-nibble encoding, used as the baseline.
+fn reference_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 16] = b"0123456789abcdef";
+    let mut out = String::with_capacity(bytes.len() * 2);
+
+    for byte in bytes {
+        out.push(char::from(ALPHABET[usize::from(byte >> 4)]));
+        out.push(char::from(ALPHABET[usize::from(byte & 0x0f)]));
+    }
+
+    out
+}
+
+/// The per-nibble decoding, used as the baseline.
+fn reference_decode(str: &str) -> Vec<u8> {
+    let nibble = |b: u8| match b {
+        b'0'..=b'9' => b - b'0',
+        b'a'..=b'f' => b - b'a' + 10,
+        b'A'..=b'F' => b - b'A' + 10,
+        _ => panic!("bad hex character"),
+    };
+
+    str.as_bytes()
+        .chunks_exact(2)
+        .map(|pair| (nibble(pair[0]) << 4) | nibble(pair[1]))
+        .collect()
+}
+
+fn main() {
+    bench("bytes_to_hex", |input| {
+        black_box(bytes_to_hex(input));
+    });
+
+    let mut out = String::with_capacity(40);
+    bench("bytes_to_hex_into", |input| {
+        out.clear();
+        bytes_to_hex_into(input, &mut out);
+        black_box(&out);
+    });
+
+    bench("bytes_to_hex_fixed", |input| {
+        black_box(bytes_to_hex_fixed(input));
+    });
+
+    let input = (0..LARGE_SIZE)
+        .map(|i| u8::try_from(i % 256).unwrap())
+        .collect::<Vec<u8>>();
+    let encoded = bytes_to_hex(&input);
+    assert_eq!(encoded, reference_encode(&input));
+    assert_eq!(
+        hex_to_bytes(encoded.as_str()).unwrap(),
+        reference_decode(&encoded)
+    );
+
+    bench_large("encode_reference", || {
+        black_box(reference_encode(black_box(&input)));
+    });
+
+    bench_large("encode_64kb", || {
+        black_box(bytes_to_hex(black_box(&input)));
+    });
+
+    bench_large("decode_reference", || {
+        black_box(reference_decode(black_box(&encoded)));
+    });
+
+    bench_large("decode_64kb", || {
+        black_box(hex_to_bytes(black_box(encoded.as_str())).unwrap());
+    });
+}
@@ -0,0 +1,54 @@
+// © 2024 Max Karou. All Rights Reserved.
+// Licensed under Apache Version 2.0, or MIT License, at your discretion.
+//
+// Apache License: http://www.apache.org/licenses/LICENSE-2.0
+// MIT License: http://opensource.org/licenses/MIT
+//
+// Usage of this file is permitted solely under a sanctioned license.
+
+//! Compares deriving the public keys of 1000 child accounts with a fresh
+//! `Secp256k1` context per key against the shared `secp256k1_context()`.
+//!
+//! Run with `cargo bench -p stacks-rs --bench secp256k1 --features wallet-sdk`.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use secp256k1::Secp256k1;
+use secp256k1::SecretKey;
+use stacks_rs::crypto::secp256k1_context;
+use stacks_rs::wallet::StacksWallet;
+
+const CHILDREN: u32 = 1000;
+
+fn bench<F>(name: &str, keys: &[SecretKey], mut f: F)
+where
+    F: FnMut(&SecretKey),
+{
+    let start = Instant::now();
+
+    for key in keys {
+        f(black_box(key));
+    }
+
+    let elapsed = start.elapsed();
+    println!("{name:<20} {elapsed:>12?}");
+}
+
+fn main() {
+    let phrase = "sound idle panel often situate develop unit text design antenna vendor screen opinion balcony share trigger accuse scatter visa uniform brass update opinion media";
+    let wallet = StacksWallet::from_secret_key(phrase).unwrap();
+
+    let keys = (0..CHILDREN)
+        .map(|index| wallet.derive_child(index, false)?.private_key())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    bench("fresh_context", &keys, |key| {
+        black_box(key.public_key(&Secp256k1::new()));
+    });
+
+    bench("cached_context", &keys, |key| {
+        black_box(key.public_key(secp256k1_context()));
+    });
+}
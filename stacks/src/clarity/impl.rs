@@ -6,12 +6,17 @@
 //
 // Usage of this file is permitted solely under a sanctioned license.
 
+use std::collections::BTreeMap;
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fmt::Display;
+use std::ops::Deref;
 
 use crate::clarity::decode_clarity_type;
 use crate::clarity::Buffer;
+use crate::clarity::Cast;
 use crate::clarity::Clarity;
+use crate::clarity::ClarityDecoder;
 use crate::clarity::Codec;
 use crate::clarity::Error;
 use crate::clarity::False;
@@ -31,29 +36,138 @@ use crate::clarity::StringUtf8;
 use crate::clarity::True;
 use crate::clarity::Tuple;
 use crate::clarity::UInt;
+use crate::clarity::UnknownClarity;
+use crate::clarity::CLARITY_MIN_VALUE_SIZE;
+use crate::clarity::CLARITY_STD_PR_SIZE;
+use crate::clarity::CLARITY_TYPE_BOOL_FALSE;
+use crate::clarity::CLARITY_TYPE_BOOL_TRUE;
+use crate::clarity::CLARITY_TYPE_CON_PR;
+use crate::clarity::CLARITY_TYPE_LIST;
+use crate::clarity::CLARITY_TYPE_OPTIONAL_NONE;
+use crate::clarity::CLARITY_TYPE_OPTIONAL_SOME;
+use crate::clarity::CLARITY_TYPE_RESPONSE_ERR;
+use crate::clarity::CLARITY_TYPE_RESPONSE_OK;
+use crate::clarity::CLARITY_TYPE_STD_PR;
+use crate::clarity::CLARITY_TYPE_TUPLE;
+use crate::clarity::CLARITY_WRAPPER_SIZE;
 use crate::crypto::bytes_to_hex;
 use crate::crypto::c32_address;
 use crate::crypto::c32_address_decode;
+use crate::crypto::hex_to_bytes;
+
+/// Maps a type identifier to the identifier of its clarity type.
+///
+/// Variants of the same type (e.g. `true` & `false`) share one identifier.
+fn clarity_type_family(id: u8) -> u8 {
+    match id {
+        CLARITY_TYPE_BOOL_FALSE => CLARITY_TYPE_BOOL_TRUE,
+        CLARITY_TYPE_CON_PR => CLARITY_TYPE_STD_PR,
+        CLARITY_TYPE_RESPONSE_ERR => CLARITY_TYPE_RESPONSE_OK,
+        CLARITY_TYPE_OPTIONAL_SOME => CLARITY_TYPE_OPTIONAL_NONE,
+        _ => id,
+    }
+}
+
+/// Returns `len` bytes of `bytes` starting at `start`.
+///
+/// Fails with `Error::LengthMismatch` instead of panicking if the input is too short.
+pub(crate) fn checked_slice(bytes: &[u8], start: usize, len: usize) -> Result<&[u8], Error> {
+    let available = bytes.len().saturating_sub(start);
+
+    if len > available {
+        return Err(Error::LengthMismatch {
+            declared: len,
+            available,
+        });
+    }
+
+    Ok(&bytes[start..start + len])
+}
+
+/// Returns the fixed-size field of `len` bytes of `bytes` starting at `start`.
+///
+/// Fails with `Error::UnexpectedEnd` instead of panicking if the input is too short,
+/// lengths declared by a prefix are read with `checked_slice`.
+#[cfg(feature = "transaction")]
+pub(crate) fn checked_read(bytes: &[u8], start: usize, len: usize) -> Result<&[u8], Error> {
+    checked_slice(bytes, start, len).map_err(|_| Error::UnexpectedEnd {
+        expected: len,
+        available: bytes.len().saturating_sub(start),
+    })
+}
+
+/// Reads the `u32` length prefix following the type identifier.
+///
+/// The prefix is validated against the remaining input, assuming each counted
+/// item occupies at least `min_size` bytes.
+fn decode_length_prefix(bytes: &[u8], min_size: usize) -> Result<usize, Error> {
+    let prefix = checked_slice(bytes, 1, 4)?;
+    let len = u32::from_be_bytes(prefix.try_into()?) as usize;
+    let available = bytes.len() - 5;
+
+    if len.saturating_mul(min_size) > available {
+        return Err(Error::LengthMismatch {
+            declared: len,
+            available,
+        });
+    }
+
+    Ok(len)
+}
+
+impl Int {
+    /// Returns the hex representation of the encoded value, without a `0x` prefix.
+    pub fn to_clarity_hex(&self) -> String {
+        let mut buff = [Self::id(); 17];
+        buff[1..].copy_from_slice(&self.__value.to_be_bytes());
+        bytes_to_hex(buff)
+    }
+
+    /// Decodes a value from its hex representation, with or without a `0x` prefix.
+    pub fn from_clarity_hex(hex: &str) -> Result<Self, Error> {
+        let hex = hex.strip_prefix("0x").unwrap_or(hex);
+        Self::decode(&hex_to_bytes(hex)?)
+    }
+}
 
 impl Codec for Int {
     fn encode(&self) -> Result<Vec<u8>, Error> {
-        let mut buff = vec![Self::id()];
-        buff.extend_from_slice(&self.__value.to_be_bytes());
+        let mut buff = vec![];
+        self.encode_into(&mut buff)?;
         Ok(buff)
     }
 
+    fn encode_into(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        out.push(Self::id());
+        out.extend_from_slice(&self.__value.to_be_bytes());
+        Ok(())
+    }
+
     fn decode(bytes: &[u8]) -> Result<Self, Error>
     where
         Self: Sized,
     {
-        if bytes[0] != Self::id() {
-            return Err(Error::BadIdentifier(Self::id(), bytes[0]));
+        let bytes = checked_slice(bytes, 0, 17)?;
+
+        let tag = checked_slice(bytes, 0, 1)?[0];
+        if tag != Self::id() {
+            return Err(Error::BadIdentifier(Self::id(), tag));
         }
 
         let mut buff = [0u8; 16];
-        buff.copy_from_slice(&bytes[1..17]);
+        buff.copy_from_slice(checked_slice(bytes, 1, 16)?);
         Ok(Self::new(i128::from_be_bytes(buff)))
     }
+
+    fn len(&self) -> Result<usize, Error> {
+        Ok(17)
+    }
+}
+
+impl Clarity for Int {
+    fn size(&self) -> usize {
+        16
+    }
 }
 
 impl Display for Int {
@@ -88,25 +202,59 @@ impl From<Int> for i128 {
     }
 }
 
+impl UInt {
+    /// Returns the hex representation of the encoded value, without a `0x` prefix.
+    pub fn to_clarity_hex(&self) -> String {
+        let mut buff = [Self::id(); 17];
+        buff[1..].copy_from_slice(&self.__value.to_be_bytes());
+        bytes_to_hex(buff)
+    }
+
+    /// Decodes a value from its hex representation, with or without a `0x` prefix.
+    pub fn from_clarity_hex(hex: &str) -> Result<Self, Error> {
+        let hex = hex.strip_prefix("0x").unwrap_or(hex);
+        Self::decode(&hex_to_bytes(hex)?)
+    }
+}
+
 impl Codec for UInt {
     fn encode(&self) -> Result<Vec<u8>, Error> {
-        let mut buff = vec![Self::id()];
-        buff.extend_from_slice(&self.__value.to_be_bytes());
+        let mut buff = vec![];
+        self.encode_into(&mut buff)?;
         Ok(buff)
     }
 
+    fn encode_into(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        out.push(Self::id());
+        out.extend_from_slice(&self.__value.to_be_bytes());
+        Ok(())
+    }
+
     fn decode(bytes: &[u8]) -> Result<Self, Error>
     where
         Self: Sized,
     {
-        if bytes[0] != Self::id() {
-            return Err(Error::BadIdentifier(Self::id(), bytes[0]));
+        let bytes = checked_slice(bytes, 0, 17)?;
+
+        let tag = checked_slice(bytes, 0, 1)?[0];
+        if tag != Self::id() {
+            return Err(Error::BadIdentifier(Self::id(), tag));
         }
 
         let mut buff = [0u8; 16];
-        buff.copy_from_slice(&bytes[1..17]);
+        buff.copy_from_slice(checked_slice(bytes, 1, 16)?);
         Ok(Self::new(u128::from_be_bytes(buff)))
     }
+
+    fn len(&self) -> Result<usize, Error> {
+        Ok(17)
+    }
+}
+
+impl Clarity for UInt {
+    fn size(&self) -> usize {
+        16
+    }
 }
 
 impl Display for UInt {
@@ -141,33 +289,68 @@ impl From<UInt> for u128 {
     }
 }
 
+impl Buffer {
+    /// Creates a new `Buffer` from the bytes of an ASCII string.
+    ///
+    /// Fails with `Error::BadStringType` on non-ASCII input.
+    pub fn from_ascii(str: &str) -> Result<Self, Error> {
+        if !str.is_ascii() {
+            return Err(Error::BadStringType("Ascii".to_string()));
+        }
+
+        Ok(Self::new(str.as_bytes().to_vec()))
+    }
+
+    /// Returns the contents of the buffer as an ASCII string.
+    ///
+    /// Fails with `Error::BadStringType` if the buffer contains non-ASCII bytes.
+    pub fn to_ascii_string(&self) -> Result<String, Error> {
+        if !self.__value.is_ascii() {
+            return Err(Error::BadStringType("Ascii".to_string()));
+        }
+
+        Ok(String::from_utf8(self.__value.clone())?)
+    }
+}
+
 impl Codec for Buffer {
     fn encode(&self) -> Result<Vec<u8>, Error> {
-        let mut buff = vec![Self::id()];
-        buff.extend_from_slice(&(u32::try_from(self.__value.len())?).to_be_bytes());
-        buff.extend_from_slice(&self.__value);
+        let mut buff = vec![];
+        self.encode_into(&mut buff)?;
         Ok(buff)
     }
 
+    fn encode_into(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        let len = u32::try_from(self.__value.len())?;
+        out.push(Self::id());
+        out.extend_from_slice(&len.to_be_bytes());
+        out.extend_from_slice(&self.__value);
+        Ok(())
+    }
+
     fn decode(bytes: &[u8]) -> Result<Self, Error>
     where
         Self: Sized,
     {
-        if bytes[0] != Self::id() {
-            return Err(Error::BadIdentifier(Self::id(), bytes[0]));
+        let tag = checked_slice(bytes, 0, 1)?[0];
+        if tag != Self::id() {
+            return Err(Error::BadIdentifier(Self::id(), tag));
         }
 
-        let len = u32::from_be_bytes([bytes[1], bytes[2], bytes[3], bytes[4]]);
-        let mut offset = 5;
+        let len = decode_length_prefix(bytes, 1)?;
+        let buff = bytes[5..5 + len].to_vec();
 
-        let mut buff = vec![];
+        Ok(Self::new(buff))
+    }
 
-        for _ in 0..len {
-            buff.push(bytes[offset]);
-            offset += 1;
-        }
+    fn len(&self) -> Result<usize, Error> {
+        Ok(5 + u32::try_from(self.__value.len())? as usize)
+    }
+}
 
-        Ok(Self::new(buff))
+impl Clarity for Buffer {
+    fn size(&self) -> usize {
+        self.__value.len()
     }
 }
 
@@ -189,6 +372,24 @@ impl Clone for Buffer {
     }
 }
 
+impl PartialOrd for Buffer {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Buffer {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.__value.cmp(&other.__value)
+    }
+}
+
+impl AsRef<[u8]> for Buffer {
+    fn as_ref(&self) -> &[u8] {
+        &self.__value
+    }
+}
+
 impl IntoIterator for Buffer {
     type IntoIter = std::vec::IntoIter<Self::Item>;
     type Item = u8;
@@ -225,12 +426,23 @@ impl Codec for True {
     where
         Self: Sized,
     {
-        if bytes[0] != Self::id() {
-            return Err(Error::BadIdentifier(Self::id(), bytes[0]));
+        let tag = checked_slice(bytes, 0, 1)?[0];
+        if tag != Self::id() {
+            return Err(Error::BadIdentifier(Self::id(), tag));
         }
 
         Ok(Self::new())
     }
+
+    fn len(&self) -> Result<usize, Error> {
+        Ok(1)
+    }
+}
+
+impl Clarity for True {
+    fn size(&self) -> usize {
+        1
+    }
 }
 
 impl Display for True {
@@ -262,12 +474,23 @@ impl Codec for False {
     where
         Self: Sized,
     {
-        if bytes[0] != Self::id() {
-            return Err(Error::BadIdentifier(Self::id(), bytes[0]));
+        let tag = checked_slice(bytes, 0, 1)?[0];
+        if tag != Self::id() {
+            return Err(Error::BadIdentifier(Self::id(), tag));
         }
 
         Ok(Self::new())
     }
+
+    fn len(&self) -> Result<usize, Error> {
+        Ok(1)
+    }
+}
+
+impl Clarity for False {
+    fn size(&self) -> usize {
+        1
+    }
 }
 
 impl Display for False {
@@ -290,6 +513,16 @@ impl Clone for False {
 
 impl Copy for False {}
 
+impl PrincipalStandard {
+    /// Creates a `PrincipalContract` for a contract deployed by this principal.
+    pub fn with_contract<S>(self, name: S) -> PrincipalContract
+    where
+        S: Into<String>,
+    {
+        PrincipalContract::new((self.__value, name.into()))
+    }
+}
+
 impl Codec for PrincipalStandard {
     fn encode(&self) -> Result<Vec<u8>, Error> {
         let (addr, ver) = c32_address_decode(&self.__value)?;
@@ -302,12 +535,19 @@ impl Codec for PrincipalStandard {
     where
         Self: Sized,
     {
-        if bytes[0] != Self::id() {
-            return Err(Error::BadIdentifier(Self::id(), bytes[0]));
+        let tag = checked_slice(bytes, 0, 1)?[0];
+        if tag != Self::id() {
+            return Err(Error::BadIdentifier(Self::id(), tag));
         }
 
-        let addr = c32_address(&bytes[2..22], bytes[1])?;
-        Ok(Self::new(addr))
+        let addr = checked_slice(bytes, 1, 21)?;
+        Ok(Self::new(c32_address(&addr[1..], addr[0])?))
+    }
+}
+
+impl Clarity for PrincipalStandard {
+    fn size(&self) -> usize {
+        CLARITY_STD_PR_SIZE
     }
 }
 
@@ -347,6 +587,13 @@ impl From<PrincipalStandard> for String {
     }
 }
 
+impl PrincipalContract {
+    /// Returns the standard principal that deployed the contract.
+    pub fn principal(&self) -> PrincipalStandard {
+        PrincipalStandard::new(self.__value.0.clone())
+    }
+}
+
 impl Codec for PrincipalContract {
     fn encode(&self) -> Result<Vec<u8>, Error> {
         let (addr, ver) = c32_address_decode(&self.__value.0)?;
@@ -361,16 +608,24 @@ impl Codec for PrincipalContract {
     where
         Self: Sized,
     {
-        if bytes[0] != Self::id() {
-            return Err(Error::BadIdentifier(Self::id(), bytes[0]));
+        let tag = checked_slice(bytes, 0, 1)?[0];
+        if tag != Self::id() {
+            return Err(Error::BadIdentifier(Self::id(), tag));
         }
 
-        let addr = c32_address(&bytes[2..22], bytes[1])?;
-        let name = String::from_utf8(bytes[23..23 + bytes[22] as usize].to_vec())?;
+        let prefix = checked_slice(bytes, 1, 22)?;
+        let addr = c32_address(&prefix[1..21], prefix[0])?;
+        let name = String::from_utf8(checked_slice(bytes, 23, prefix[21] as usize)?.to_vec())?;
         Ok(Self::new((addr, name)))
     }
 }
 
+impl Clarity for PrincipalContract {
+    fn size(&self) -> usize {
+        CLARITY_STD_PR_SIZE + 1 + self.__value.1.len()
+    }
+}
+
 impl Display for PrincipalContract {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}.{}", self.__value.0, self.__value.1)
@@ -403,17 +658,23 @@ impl From<(&str, &str)> for PrincipalContract {
 
 impl Codec for ResponseOk {
     fn encode(&self) -> Result<Vec<u8>, Error> {
-        let mut buff = vec![Self::id()];
-        buff.extend_from_slice(&self.__value.encode()?);
+        let mut buff = vec![];
+        self.encode_into(&mut buff)?;
         Ok(buff)
     }
 
+    fn encode_into(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        out.push(Self::id());
+        self.__value.encode_into(out)
+    }
+
     fn decode(bytes: &[u8]) -> Result<Self, Error>
     where
         Self: Sized,
     {
-        if bytes[0] != Self::id() {
-            return Err(Error::BadIdentifier(Self::id(), bytes[0]));
+        let tag = checked_slice(bytes, 0, 1)?[0];
+        if tag != Self::id() {
+            return Err(Error::BadIdentifier(Self::id(), tag));
         }
 
         let decoded = decode_clarity_type(&bytes[1..])?;
@@ -421,6 +682,12 @@ impl Codec for ResponseOk {
     }
 }
 
+impl Clarity for ResponseOk {
+    fn size(&self) -> usize {
+        CLARITY_WRAPPER_SIZE + self.__value.size()
+    }
+}
+
 impl Display for ResponseOk {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "(ok {})", self.__value)
@@ -441,17 +708,23 @@ impl Clone for ResponseOk {
 
 impl Codec for ResponseErr {
     fn encode(&self) -> Result<Vec<u8>, Error> {
-        let mut buff = vec![Self::id()];
-        buff.extend_from_slice(&self.__value.encode()?);
+        let mut buff = vec![];
+        self.encode_into(&mut buff)?;
         Ok(buff)
     }
 
+    fn encode_into(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        out.push(Self::id());
+        self.__value.encode_into(out)
+    }
+
     fn decode(bytes: &[u8]) -> Result<Self, Error>
     where
         Self: Sized,
     {
-        if bytes[0] != Self::id() {
-            return Err(Error::BadIdentifier(Self::id(), bytes[0]));
+        let tag = checked_slice(bytes, 0, 1)?[0];
+        if tag != Self::id() {
+            return Err(Error::BadIdentifier(Self::id(), tag));
         }
 
         let decoded = decode_clarity_type(&bytes[1..])?;
@@ -459,6 +732,12 @@ impl Codec for ResponseErr {
     }
 }
 
+impl Clarity for ResponseErr {
+    fn size(&self) -> usize {
+        CLARITY_WRAPPER_SIZE + self.__value.size()
+    }
+}
+
 impl Display for ResponseErr {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "(err {})", self.__value)
@@ -479,17 +758,23 @@ impl Clone for ResponseErr {
 
 impl Codec for OptionalSome {
     fn encode(&self) -> Result<Vec<u8>, Error> {
-        let mut buff = vec![Self::id()];
-        buff.extend_from_slice(&self.__value.encode()?);
+        let mut buff = vec![];
+        self.encode_into(&mut buff)?;
         Ok(buff)
     }
 
+    fn encode_into(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        out.push(Self::id());
+        self.__value.encode_into(out)
+    }
+
     fn decode(bytes: &[u8]) -> Result<Self, Error>
     where
         Self: Sized,
     {
-        if bytes[0] != Self::id() {
-            return Err(Error::BadIdentifier(Self::id(), bytes[0]));
+        let tag = checked_slice(bytes, 0, 1)?[0];
+        if tag != Self::id() {
+            return Err(Error::BadIdentifier(Self::id(), tag));
         }
 
         let decoded = decode_clarity_type(&bytes[1..])?;
@@ -497,6 +782,12 @@ impl Codec for OptionalSome {
     }
 }
 
+impl Clarity for OptionalSome {
+    fn size(&self) -> usize {
+        CLARITY_WRAPPER_SIZE + self.__value.size()
+    }
+}
+
 impl Display for OptionalSome {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "(some {})", self.__value)
@@ -524,12 +815,23 @@ impl Codec for OptionalNone {
     where
         Self: Sized,
     {
-        if bytes[0] != Self::id() {
-            return Err(Error::BadIdentifier(Self::id(), bytes[0]));
+        let tag = checked_slice(bytes, 0, 1)?[0];
+        if tag != Self::id() {
+            return Err(Error::BadIdentifier(Self::id(), tag));
         }
 
         Ok(Self::new())
     }
+
+    fn len(&self) -> Result<usize, Error> {
+        Ok(1)
+    }
+}
+
+impl Clarity for OptionalNone {
+    fn size(&self) -> usize {
+        1
+    }
 }
 
 impl Display for OptionalNone {
@@ -552,30 +854,159 @@ impl Clone for OptionalNone {
 
 impl Copy for OptionalNone {}
 
+impl List {
+    /// Returns the number of elements.
+    pub fn count(&self) -> usize {
+        self.__value.len()
+    }
+
+    /// Returns `true` if the list contains no elements.
+    ///
+    /// Unlike `Codec::is_empty`, this checks the element count rather than
+    /// the encoded bytes, which always include the type prefix.
+    pub fn is_empty(&self) -> bool {
+        self.__value.is_empty()
+    }
+
+    /// Gets a value by index & casts it into a concrete type.
+    pub fn get_as<T>(&self, index: usize) -> Result<T, Error>
+    where
+        T: Clarity,
+    {
+        let value = self
+            .__value
+            .get(index)
+            .ok_or(Error::IndexOutOfBounds(index, self.__value.len()))?;
+
+        value.clone().cast::<T>()
+    }
+
+    /// Concatenates two lists.
+    ///
+    /// Fails if the element types of both lists differ, see `check_same_type`.
+    pub fn concat(mut self, other: List) -> Result<List, Error> {
+        check_same_types(self.__value.iter().chain(other.__value.iter()))?;
+        self.__value.extend(other.__value);
+        Ok(self)
+    }
+}
+
+/// Checks that all values have the same type as the first, see `check_same_type`.
+///
+/// Values are only compared against the first, if its type isn't fully determined
+/// (e.g. `none`) the others may still differ from each other.
+fn check_same_types<'a, I>(mut values: I) -> Result<(), Error>
+where
+    I: Iterator<Item = &'a Box<dyn Clarity>>,
+{
+    if let Some(expected) = values.next() {
+        for value in values {
+            check_same_type(expected.as_ref(), value.as_ref())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Downcasts a clarity value to its concrete type.
+fn downcast<T>(value: &dyn Clarity) -> Result<&T, Error>
+where
+    T: Clarity,
+{
+    value.as_any().downcast_ref::<T>().ok_or(Error::BadDowncast)
+}
+
+/// Checks that two values have the same type, as elements of one list must.
+///
+/// Compares the type identifiers recursively, tuples must have the same keys.
+/// Parts of a type a value doesn't determine match any type, e.g. the inner type
+/// of `none`, the elements of an empty list or the `err` type of an `ok` response.
+/// Lengths of sequences are not compared.
+fn check_same_type(expected: &dyn Clarity, received: &dyn Clarity) -> Result<(), Error> {
+    let expected_id = expected.encode()?[0];
+    let received_id = received.encode()?[0];
+
+    if clarity_type_family(expected_id) != clarity_type_family(received_id) {
+        return Err(Error::BadIdentifier(
+            clarity_type_family(expected_id),
+            clarity_type_family(received_id),
+        ));
+    }
+
+    match (expected_id, received_id) {
+        (CLARITY_TYPE_OPTIONAL_SOME, CLARITY_TYPE_OPTIONAL_SOME) => check_same_type(
+            downcast::<OptionalSome>(expected)?.value().as_ref(),
+            downcast::<OptionalSome>(received)?.value().as_ref(),
+        ),
+        (CLARITY_TYPE_RESPONSE_OK, CLARITY_TYPE_RESPONSE_OK) => check_same_type(
+            downcast::<ResponseOk>(expected)?.value().as_ref(),
+            downcast::<ResponseOk>(received)?.value().as_ref(),
+        ),
+        (CLARITY_TYPE_RESPONSE_ERR, CLARITY_TYPE_RESPONSE_ERR) => check_same_type(
+            downcast::<ResponseErr>(expected)?.value().as_ref(),
+            downcast::<ResponseErr>(received)?.value().as_ref(),
+        ),
+        (CLARITY_TYPE_LIST, CLARITY_TYPE_LIST) => {
+            let expected = downcast::<List>(expected)?.value();
+            let received = downcast::<List>(received)?.value();
+            check_same_types(expected.iter().chain(received.iter()))
+        }
+        (CLARITY_TYPE_TUPLE, CLARITY_TYPE_TUPLE) => {
+            let expected = downcast::<Tuple>(expected)?.value();
+            let received = downcast::<Tuple>(received)?.value();
+
+            for (key, value) in expected {
+                let (_, other) = received
+                    .iter()
+                    .find(|(other, _)| other == key)
+                    .ok_or_else(|| Error::KeyNotFound(key.clone()))?;
+                check_same_type(value.as_ref(), other.as_ref())?;
+            }
+
+            match received
+                .iter()
+                .find(|(key, _)| !expected.iter().any(|(k, _)| k == key))
+            {
+                Some((key, _)) => Err(Error::KeyNotFound(key.clone())),
+                None => Ok(()),
+            }
+        }
+        _ => Ok(()),
+    }
+}
+
 impl Codec for List {
     fn encode(&self) -> Result<Vec<u8>, Error> {
-        let mut buff = vec![Self::id()];
-        buff.extend_from_slice(&(u32::try_from(self.__value.len())?).to_be_bytes());
+        let mut buff = vec![];
+        self.encode_into(&mut buff)?;
+        Ok(buff)
+    }
+
+    fn encode_into(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        let len = u32::try_from(self.__value.len())?;
+        out.push(Self::id());
+        out.extend_from_slice(&len.to_be_bytes());
 
         for item in &self.__value {
-            buff.extend_from_slice(&item.encode()?);
+            item.encode_into(out)?;
         }
 
-        Ok(buff)
+        Ok(())
     }
 
     fn decode(bytes: &[u8]) -> Result<Self, Error>
     where
         Self: Sized,
     {
-        if bytes[0] != Self::id() {
-            return Err(Error::BadIdentifier(Self::id(), bytes[0]));
+        let tag = checked_slice(bytes, 0, 1)?[0];
+        if tag != Self::id() {
+            return Err(Error::BadIdentifier(Self::id(), tag));
         }
 
-        let len = u32::from_be_bytes([bytes[1], bytes[2], bytes[3], bytes[4]]);
+        let len = decode_length_prefix(bytes, CLARITY_MIN_VALUE_SIZE)?;
 
         let mut offset = 5;
-        let mut values = vec![];
+        let mut values = Vec::with_capacity(len);
 
         for _ in 0..len {
             let value = decode_clarity_type(&bytes[offset..])?;
@@ -587,6 +1018,12 @@ impl Codec for List {
     }
 }
 
+impl Clarity for List {
+    fn size(&self) -> usize {
+        self.__value.iter().map(|value| value.size()).sum()
+    }
+}
+
 impl Display for List {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "(list ")?;
@@ -629,6 +1066,19 @@ impl IntoIterator for List {
 }
 
 impl Tuple {
+    /// Returns the number of entries.
+    pub fn count(&self) -> usize {
+        self.__value.len()
+    }
+
+    /// Returns `true` if the tuple contains no entries.
+    ///
+    /// Unlike `Codec::is_empty`, this checks the element count rather than
+    /// the encoded bytes, which always include the type prefix.
+    pub fn is_empty(&self) -> bool {
+        self.__value.is_empty()
+    }
+
     /// Gets a value by key.
     pub fn get<T>(&self, key: T) -> Option<Box<dyn Clarity>>
     where
@@ -640,6 +1090,20 @@ impl Tuple {
             .map(|(_, v)| v.clone())
     }
 
+    /// Gets a value by key & casts it into a concrete type.
+    pub fn get_as<T, K>(&self, key: K) -> Result<T, Error>
+    where
+        T: Clarity,
+        K: AsRef<str>,
+    {
+        let key = key.as_ref();
+        let value = self
+            .get(key)
+            .ok_or_else(|| Error::KeyNotFound(key.to_string()))?;
+
+        value.cast::<T>()
+    }
+
     /// Gets a mutable value by key.
     pub fn get_mut<T>(&mut self, key: T) -> Option<&mut Box<dyn Clarity>>
     where
@@ -659,6 +1123,21 @@ impl Tuple {
         self.__value.push((key, Box::new(value)));
     }
 
+    /// Merges two tuples.
+    ///
+    /// Fails if a key is present in both tuples.
+    pub fn merge(mut self, other: Tuple) -> Result<Tuple, Error> {
+        for (key, value) in other.__value {
+            if self.get(&key).is_some() {
+                return Err(Error::DuplicateKey(key));
+            }
+
+            self.__value.push((key, value));
+        }
+
+        Ok(self)
+    }
+
     /// Removes a key-value pair from the tuple.
     ///
     /// Returns the value if the key was present in the tuple.
@@ -682,37 +1161,66 @@ impl Tuple {
     pub fn values(&self) -> impl Iterator<Item = &Box<dyn Clarity>> {
         self.__value.iter().map(|(_, v)| v)
     }
+
+    /// Converts the tuple into a map, ordered by key.
+    ///
+    /// If a key is present more than once, the last value is kept.
+    pub fn into_map(self) -> BTreeMap<String, Box<dyn Clarity>> {
+        self.__value.into_iter().collect()
+    }
+}
+
+impl From<BTreeMap<String, Box<dyn Clarity>>> for Tuple {
+    /// Creates a tuple with its keys in canonical (sorted) order.
+    fn from(map: BTreeMap<String, Box<dyn Clarity>>) -> Self {
+        Self::new(map.into_iter().collect())
+    }
+}
+
+impl From<HashMap<String, Box<dyn Clarity>>> for Tuple {
+    /// Creates a tuple with its keys in canonical (sorted) order.
+    fn from(map: HashMap<String, Box<dyn Clarity>>) -> Self {
+        Self::from(map.into_iter().collect::<BTreeMap<_, _>>())
+    }
 }
 
 impl Codec for Tuple {
     fn encode(&self) -> Result<Vec<u8>, Error> {
-        let mut buff = vec![Self::id()];
-        buff.extend_from_slice(&u32::try_from(self.__value.len())?.to_be_bytes());
+        let mut buff = vec![];
+        self.encode_into(&mut buff)?;
+        Ok(buff)
+    }
+
+    fn encode_into(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        let len = u32::try_from(self.__value.len())?;
+        out.push(Self::id());
+        out.extend_from_slice(&len.to_be_bytes());
 
         for (k, v) in &self.__value {
-            buff.extend_from_slice(&LengthPrefixedStr::new((*k).to_string()).encode()?);
-            buff.extend_from_slice(&v.encode()?);
+            LengthPrefixedStr::new(k.clone()).encode_into(out)?;
+            v.encode_into(out)?;
         }
 
-        Ok(buff)
+        Ok(())
     }
 
     fn decode(bytes: &[u8]) -> Result<Self, Error>
     where
         Self: Sized,
     {
-        if bytes[0] != Self::id() {
-            return Err(Error::BadIdentifier(Self::id(), bytes[0]));
+        let tag = checked_slice(bytes, 0, 1)?[0];
+        if tag != Self::id() {
+            return Err(Error::BadIdentifier(Self::id(), tag));
         }
 
-        let len = u32::from_be_bytes([bytes[1], bytes[2], bytes[3], bytes[4]]);
+        let len = decode_length_prefix(bytes, 1 + CLARITY_MIN_VALUE_SIZE)?;
 
         let mut offset = 5;
-        let mut values = vec![];
+        let mut values = Vec::with_capacity(len);
 
         for _ in 0..len {
-            let k_len = bytes[offset] as usize;
-            let key = String::from_utf8((bytes[offset + 1..offset + 1 + k_len]).to_vec())?;
+            let k_len = checked_slice(bytes, offset, 1)?[0] as usize;
+            let key = String::from_utf8(checked_slice(bytes, offset + 1, k_len)?.to_vec())?;
             offset += 1 + k_len;
             let value = decode_clarity_type(&bytes[offset..])?;
             offset += value.encode()?.len();
@@ -723,6 +1231,15 @@ impl Codec for Tuple {
     }
 }
 
+impl Clarity for Tuple {
+    fn size(&self) -> usize {
+        self.__value
+            .iter()
+            .map(|(key, value)| key.len() + value.size())
+            .sum()
+    }
+}
+
 impl Display for Tuple {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "(tuple ")?;
@@ -782,14 +1299,29 @@ impl Codec for StringAscii {
     where
         Self: Sized,
     {
-        if bytes[0] != Self::id() {
-            return Err(Error::BadIdentifier(Self::id(), bytes[0]));
+        let tag = checked_slice(bytes, 0, 1)?[0];
+        if tag != Self::id() {
+            return Err(Error::BadIdentifier(Self::id(), tag));
         }
 
-        let len = u32::from_be_bytes([bytes[1], bytes[2], bytes[3], bytes[4]]);
-        let str = String::from_utf8(bytes[5..(5 + len as usize)].to_vec())?;
+        let len = decode_length_prefix(bytes, 1)?;
+        let str = String::from_utf8(bytes[5..5 + len].to_vec())?;
         Ok(Self::new(str))
     }
+
+    fn len(&self) -> Result<usize, Error> {
+        if !self.__value.is_ascii() {
+            return Err(Error::BadStringType("Ascii".to_string()));
+        }
+
+        Ok(5 + u32::try_from(self.__value.len())? as usize)
+    }
+}
+
+impl Clarity for StringAscii {
+    fn size(&self) -> usize {
+        self.__value.len()
+    }
 }
 
 impl Display for StringAscii {
@@ -810,6 +1342,20 @@ impl Clone for StringAscii {
     }
 }
 
+impl AsRef<str> for StringAscii {
+    fn as_ref(&self) -> &str {
+        &self.__value
+    }
+}
+
+impl Deref for StringAscii {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.__value
+    }
+}
+
 impl Codec for StringUtf8 {
     fn encode(&self) -> Result<Vec<u8>, Error> {
         let mut buff = vec![Self::id()];
@@ -823,10 +1369,20 @@ impl Codec for StringUtf8 {
     where
         Self: Sized,
     {
-        let len = u32::from_be_bytes([bytes[1], bytes[2], bytes[3], bytes[4]]);
-        let str = String::from_utf8(bytes[5..(5 + len as usize)].to_vec())?;
+        let len = decode_length_prefix(bytes, 1)?;
+        let str = String::from_utf8(bytes[5..5 + len].to_vec())?;
         Ok(Self::new(str))
     }
+
+    fn len(&self) -> Result<usize, Error> {
+        Ok(5 + u32::try_from(self.__value.len())? as usize)
+    }
+}
+
+impl Clarity for StringUtf8 {
+    fn size(&self) -> usize {
+        4 * self.__value.chars().count()
+    }
 }
 
 impl Display for StringUtf8 {
@@ -847,30 +1403,56 @@ impl Clone for StringUtf8 {
     }
 }
 
+impl AsRef<str> for StringUtf8 {
+    fn as_ref(&self) -> &str {
+        &self.__value
+    }
+}
+
+impl Deref for StringUtf8 {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.__value
+    }
+}
+
 impl Codec for LengthPrefixedStr {
     fn encode(&self) -> Result<Vec<u8>, Error> {
         let mut buff = vec![];
+        self.encode_into(&mut buff)?;
+        Ok(buff)
+    }
+
+    fn encode_into(&self, out: &mut Vec<u8>) -> Result<(), Error> {
         let bytes = self.__value.as_bytes();
 
         if bytes.len() > 128 {
             return Err(Error::BadStringLength(bytes.len(), 128));
         }
 
-        buff.extend_from_slice(&u8::try_from(bytes.len())?.to_be_bytes());
-        buff.extend_from_slice(bytes);
-        Ok(buff)
+        out.push(u8::try_from(bytes.len())?);
+        out.extend_from_slice(bytes);
+        Ok(())
     }
 
     fn decode(bytes: &[u8]) -> Result<Self, Error>
     where
         Self: Sized,
     {
+        let len = checked_slice(bytes, 0, 1)?[0] as usize;
         Ok(Self::new(String::from_utf8(
-            bytes[1..=(bytes[0] as usize)].to_vec(),
+            checked_slice(bytes, 1, len)?.to_vec(),
         )?))
     }
 }
 
+impl Clarity for LengthPrefixedStr {
+    fn size(&self) -> usize {
+        self.__value.len()
+    }
+}
+
 impl Display for LengthPrefixedStr {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.__value)
@@ -889,6 +1471,20 @@ impl Clone for LengthPrefixedStr {
     }
 }
 
+impl AsRef<str> for LengthPrefixedStr {
+    fn as_ref(&self) -> &str {
+        &self.__value
+    }
+}
+
+impl Deref for LengthPrefixedStr {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.__value
+    }
+}
+
 impl From<String> for LengthPrefixedStr {
     fn from(value: String) -> Self {
         Self::new(value)
@@ -901,38 +1497,76 @@ impl From<&str> for LengthPrefixedStr {
     }
 }
 
+impl FnArguments {
+    /// Returns the number of arguments.
+    pub fn count(&self) -> usize {
+        self.__value.len()
+    }
+
+    /// Returns `true` if the argument list contains no arguments.
+    ///
+    /// Unlike `Codec::is_empty`, this checks the element count rather than
+    /// the encoded bytes, which always include the type prefix.
+    pub fn is_empty(&self) -> bool {
+        self.__value.is_empty()
+    }
+}
+
 impl Codec for FnArguments {
     fn encode(&self) -> Result<Vec<u8>, Error> {
         let mut buff = vec![];
-        buff.extend_from_slice(&u32::try_from(self.__value.len())?.to_be_bytes());
+        self.encode_into(&mut buff)?;
+        Ok(buff)
+    }
+
+    fn encode_into(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        out.extend_from_slice(&u32::try_from(self.__value.len())?.to_be_bytes());
 
         for arg in &self.__value {
-            buff.extend_from_slice(&arg.encode()?);
+            arg.encode_into(out)?;
         }
 
-        Ok(buff)
+        Ok(())
     }
 
     fn decode(bytes: &[u8]) -> Result<Self, Error>
     where
         Self: Sized,
     {
-        let (len, mut remainder) = bytes.split_at(4);
+        let len = checked_slice(bytes, 0, 4)?;
         let num = u32::from_be_bytes(len.try_into()?) as usize;
+        let remainder = &bytes[4..];
+
+        if num.saturating_mul(CLARITY_MIN_VALUE_SIZE) > remainder.len() {
+            return Err(Error::LengthMismatch {
+                declared: num,
+                available: remainder.len(),
+            });
+        }
 
         let mut __value = Vec::with_capacity(num);
 
-        for _ in 0..num {
-            let arg = decode_clarity_type(remainder)?;
+        for value in ClarityDecoder::new(remainder).take(num) {
+            __value.push(value?);
+        }
 
-            remainder = &remainder[arg.len()?..];
-            __value.push(arg);
+        if __value.len() < num {
+            return Err(Error::LengthMismatch {
+                declared: num,
+                available: __value.len(),
+            });
         }
 
         Ok(FnArguments { __value })
     }
 }
 
+impl Clarity for FnArguments {
+    fn size(&self) -> usize {
+        self.__value.iter().map(|value| value.size()).sum()
+    }
+}
+
 impl Display for FnArguments {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "(fn-args ")?;
@@ -974,6 +1608,62 @@ impl IntoIterator for FnArguments {
     }
 }
 
+impl UnknownClarity {
+    /// Returns the unrecognized type identifier.
+    pub fn tag(&self) -> u8 {
+        self.__value.0
+    }
+
+    /// Returns the raw bytes following the type identifier.
+    pub fn bytes(&self) -> &[u8] {
+        &self.__value.1
+    }
+}
+
+impl Codec for UnknownClarity {
+    fn encode(&self) -> Result<Vec<u8>, Error> {
+        let mut buff = vec![self.__value.0];
+        buff.extend_from_slice(&self.__value.1);
+        Ok(buff)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        let (tag, rest) = bytes.split_first().ok_or(Error::LengthMismatch {
+            declared: 1,
+            available: 0,
+        })?;
+
+        Ok(Self::new((*tag, rest.to_vec())))
+    }
+}
+
+impl Clarity for UnknownClarity {
+    fn size(&self) -> usize {
+        self.__value.1.len()
+    }
+}
+
+impl Display for UnknownClarity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0x{}", bytes_to_hex(self.encode().unwrap_or_default()))
+    }
+}
+
+impl Debug for UnknownClarity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "UnknownClarity({:#04x}, {self})", self.__value.0)
+    }
+}
+
+impl Clone for UnknownClarity {
+    fn clone(&self) -> Self {
+        Self::new(self.__value.clone())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use rand::thread_rng;
@@ -981,7 +1671,7 @@ mod tests {
 
     use super::*;
     use crate::clarity;
-    use crate::clarity::Cast;
+    use crate::clarity::decode_clarity_type_lenient;
     use crate::clarity::Codec;
     use crate::crypto::hex::bytes_to_hex;
     use crate::crypto::hex::hex_to_bytes;
@@ -1014,6 +1704,32 @@ mod tests {
         assert_eq!(uint, UInt::decode(&bytes).unwrap());
     }
 
+    #[test]
+    fn test_clarity_int_clarity_hex() {
+        assert_eq!(
+            Int::new(1).to_clarity_hex(),
+            "0000000000000000000000000000000001"
+        );
+        assert_eq!(
+            Int::new(-1).to_clarity_hex(),
+            "00ffffffffffffffffffffffffffffffff"
+        );
+        assert_eq!(
+            UInt::new(1).to_clarity_hex(),
+            "0100000000000000000000000000000001"
+        );
+
+        let int = Int::from_clarity_hex("00ffffffffffffffffffffffffffffffff").unwrap();
+        assert_eq!(int.into_value(), -1);
+
+        let uint = UInt::from_clarity_hex("0x0100000000000000000000000000000001").unwrap();
+        assert_eq!(uint.into_value(), 1);
+
+        assert!(Int::from_clarity_hex("0100000000000000000000000000000001").is_err());
+        assert!(UInt::from_clarity_hex("0x01").is_err());
+        assert!(UInt::from_clarity_hex("zz").is_err());
+    }
+
     #[test]
     fn test_clarity_int_randomized_value() {
         let mut rng = thread_rng();
@@ -1081,6 +1797,55 @@ mod tests {
         assert_eq!(buffer.to_string(), "0xdeadbeef");
     }
 
+    #[test]
+    fn test_clarity_buffer_ascii() {
+        let buffer = Buffer::from_ascii("hello").unwrap();
+        assert_eq!(buffer.value(), b"hello");
+        assert_eq!(buffer.to_ascii_string().unwrap(), "hello");
+        assert_eq!(
+            Buffer::from_ascii("").unwrap().to_ascii_string().unwrap(),
+            ""
+        );
+
+        assert!(matches!(
+            Buffer::from_ascii("hello 🌾"),
+            Err(Error::BadStringType(_))
+        ));
+        assert!(matches!(
+            clarity!(Buffer, [0x68, 0xff]).to_ascii_string(),
+            Err(Error::BadStringType(_))
+        ));
+    }
+
+    #[test]
+    fn test_clarity_buffer_ord() {
+        let mut buffers = [
+            clarity!(Buffer, [0x02]),
+            clarity!(Buffer, [0x01, 0xff]),
+            clarity!(Buffer, [0x01]),
+            clarity!(Buffer),
+            clarity!(Buffer, [0x01, 0x00]),
+        ];
+        buffers.sort();
+
+        let sorted = buffers.iter().map(Buffer::value).collect::<Vec<_>>();
+        assert_eq!(
+            sorted,
+            [
+                &vec![],
+                &vec![0x01],
+                &vec![0x01, 0x00],
+                &vec![0x01, 0xff],
+                &vec![0x02]
+            ]
+        );
+
+        let a = clarity!(Buffer, [0xab, 0xcd]);
+        let b = clarity!(Buffer, [0xab, 0xcd]);
+        assert_eq!(a.cmp(&b), std::cmp::Ordering::Equal);
+        assert_eq!(a, b);
+    }
+
     #[test]
     fn test_clarity_true_roundtrip() {
         let t = clarity!(True);
@@ -1135,6 +1900,21 @@ mod tests {
         assert_eq!(hex, expected_hex);
     }
 
+    #[test]
+    fn test_clarity_principal_conversion() {
+        let addr = "STB44HYPYAT2BB2QE513NSP81HTMYWBJP02HPGK6";
+        let std = clarity!(PrincipalStandard, addr);
+
+        let con = std.clone().with_contract("abcd");
+        assert_eq!(con, clarity!(PrincipalContract, addr, "abcd"));
+        assert_eq!(con.value().1, "abcd");
+
+        let back = con.principal();
+        assert_eq!(back, std);
+        assert_eq!(back.value(), addr);
+        assert_eq!(back.encode().unwrap(), std.encode().unwrap());
+    }
+
     #[test]
     fn test_clarity_principal_display() {
         let addr = "STB44HYPYAT2BB2QE513NSP81HTMYWBJP02HPGK6";
@@ -1325,6 +2105,178 @@ mod tests {
         assert!(iter.next().is_none());
     }
 
+    #[test]
+    fn test_clarity_tuple_get_as() {
+        let tuple = clarity!(Tuple, ("a", clarity!(Int, 1)), ("b", clarity!(UInt, 1)));
+
+        assert_eq!(tuple.get_as::<Int, _>("a").unwrap(), Int::new(1));
+        assert_eq!(tuple.get_as::<UInt, _>("b").unwrap(), UInt::new(1));
+
+        assert!(matches!(
+            tuple.get_as::<UInt, _>("a"),
+            Err(Error::BadDowncast)
+        ));
+        assert!(matches!(
+            tuple.get_as::<Int, _>("c"),
+            Err(Error::KeyNotFound(key)) if key == "c"
+        ));
+    }
+
+    #[test]
+    fn test_clarity_list_get_as() {
+        let list = clarity!(List, clarity!(Int, 1), clarity!(True));
+
+        assert_eq!(list.get_as::<Int>(0).unwrap(), Int::new(1));
+        assert_eq!(list.get_as::<True>(1).unwrap(), True::new());
+
+        assert!(matches!(list.get_as::<False>(1), Err(Error::BadDowncast)));
+        assert!(matches!(
+            list.get_as::<Int>(2),
+            Err(Error::IndexOutOfBounds(2, 2))
+        ));
+    }
+
+    #[test]
+    fn test_clarity_as_ref_deref() {
+        let buffer = clarity!(Buffer, b"test".to_vec());
+        assert_eq!(buffer.as_ref(), b"test");
+
+        let ascii = clarity!(StringAscii, "hello world");
+        assert!(ascii.starts_with("hello"));
+        assert_eq!(ascii.to_uppercase(), "HELLO WORLD");
+        assert_eq!(AsRef::<str>::as_ref(&ascii).len(), 11);
+
+        let utf8 = clarity!(StringUtf8, "hello \u{1F30E}");
+        assert!(utf8.ends_with('\u{1F30E}'));
+        assert_eq!(utf8.chars().count(), 7);
+
+        let name = LengthPrefixedStr::from("my-contract");
+        assert_eq!(name.split('-').collect::<Vec<_>>(), ["my", "contract"]);
+        assert_eq!(&*name, "my-contract");
+    }
+
+    #[test]
+    fn test_clarity_boxed_eq() {
+        let a: Box<dyn Clarity> = Box::new(clarity!(UInt, 1));
+        let b: Box<dyn Clarity> = Box::new(clarity!(UInt, 1));
+        let c: Box<dyn Clarity> = Box::new(clarity!(UInt, 2));
+        let d: Box<dyn Clarity> = Box::new(clarity!(Int, 1));
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_ne!(a, d);
+        assert!(a.clarity_eq(&clarity!(UInt, 1)));
+
+        let tuple = clarity!(Tuple, ("a", clarity!(UInt, 1)), ("b", clarity!(True)));
+        let decoded = decode_clarity_type(&tuple.encode().unwrap()).unwrap();
+        let expected: Box<dyn Clarity> = Box::new(tuple);
+        assert_eq!(decoded, expected);
+
+        let invalid: Box<dyn Clarity> = Box::new(clarity!(StringAscii, "\u{1F30E}"));
+        assert_eq!(invalid, invalid.clone());
+        assert_ne!(
+            invalid,
+            Box::new(clarity!(StringAscii, "\u{1F30D}")) as Box<dyn Clarity>
+        );
+        assert_ne!(
+            invalid,
+            Box::new(clarity!(StringAscii, "a")) as Box<dyn Clarity>
+        );
+    }
+
+    #[test]
+    fn test_clarity_fixed_width_len() {
+        let values: Vec<Box<dyn Clarity>> = vec![
+            Box::new(clarity!(Int, -1)),
+            Box::new(clarity!(UInt, 1)),
+            Box::new(clarity!(True)),
+            Box::new(clarity!(False)),
+            Box::new(clarity!(OptionalNone)),
+            Box::new(clarity!(Buffer, Vec::new())),
+            Box::new(clarity!(Buffer, b"test".to_vec())),
+            Box::new(clarity!(StringAscii, "")),
+            Box::new(clarity!(StringAscii, "hello world")),
+            Box::new(clarity!(StringUtf8, "hello \u{1F30E}")),
+        ];
+
+        for value in values {
+            assert_eq!(value.len().unwrap(), value.encode().unwrap().len());
+        }
+
+        assert!(clarity!(StringAscii, "\u{1F30E}").len().is_err());
+    }
+
+    #[test]
+    fn test_clarity_list_concat() {
+        let a = clarity!(List, clarity!(UInt, 1), clarity!(UInt, 2));
+        let b = clarity!(List, clarity!(UInt, 3));
+
+        let list = a.clone().concat(b).unwrap();
+        let expected = clarity!(
+            List,
+            clarity!(UInt, 1),
+            clarity!(UInt, 2),
+            clarity!(UInt, 3)
+        );
+        assert_eq!(list.encode().unwrap(), expected.encode().unwrap());
+
+        let bools = clarity!(List, clarity!(True)).concat(clarity!(List, clarity!(False)));
+        assert!(bools.is_ok());
+
+        let empty = clarity!(List).concat(a.clone()).unwrap();
+        assert_eq!(empty.encode().unwrap(), a.encode().unwrap());
+
+        let mixed = a.concat(clarity!(List, clarity!(Int, 3)));
+        assert!(matches!(mixed, Err(Error::BadIdentifier(0x01, 0x00))));
+
+        let some = |value: UInt| clarity!(List, clarity!(OptionalSome, value));
+        let optionals = clarity!(List, clarity!(OptionalNone))
+            .concat(some(clarity!(UInt, 1)))
+            .unwrap();
+        assert!(optionals.concat(some(clarity!(UInt, 2))).is_ok());
+
+        let optionals = some(clarity!(UInt, 1))
+            .concat(clarity!(List, clarity!(OptionalSome, clarity!(Int, 1))));
+        assert!(matches!(optionals, Err(Error::BadIdentifier(0x01, 0x00))));
+
+        let nested = clarity!(List, clarity!(List, clarity!(UInt, 1)));
+        assert!(nested
+            .clone()
+            .concat(clarity!(List, clarity!(List)))
+            .is_ok());
+        let nested = nested.concat(clarity!(List, clarity!(List, clarity!(True))));
+        assert!(matches!(nested, Err(Error::BadIdentifier(0x01, 0x03))));
+
+        let tuple = |key: &str, value: UInt| clarity!(List, clarity!(Tuple, (key, value)));
+        assert!(tuple("a", clarity!(UInt, 1))
+            .concat(tuple("a", clarity!(UInt, 2)))
+            .is_ok());
+
+        let keys = tuple("a", clarity!(UInt, 1)).concat(tuple("b", clarity!(UInt, 1)));
+        assert!(matches!(keys, Err(Error::KeyNotFound(key)) if key == "a"));
+
+        let members = tuple("a", clarity!(UInt, 1))
+            .concat(clarity!(List, clarity!(Tuple, ("a", clarity!(Int, 1)))));
+        assert!(matches!(members, Err(Error::BadIdentifier(0x01, 0x00))));
+
+        let responses = clarity!(List, clarity!(ResponseOk, clarity!(UInt, 1)))
+            .concat(clarity!(List, clarity!(ResponseErr, clarity!(Int, 1))));
+        assert!(responses.is_ok());
+    }
+
+    #[test]
+    fn test_clarity_tuple_merge() {
+        let a = clarity!(Tuple, ("a", clarity!(Int, 1)));
+        let b = clarity!(Tuple, ("b", clarity!(UInt, 1)));
+
+        let tuple = a.clone().merge(b).unwrap();
+        let expected = clarity!(Tuple, ("a", clarity!(Int, 1)), ("b", clarity!(UInt, 1)));
+        assert_eq!(tuple.encode().unwrap(), expected.encode().unwrap());
+
+        let overlap = a.merge(clarity!(Tuple, ("a", clarity!(UInt, 2))));
+        assert!(matches!(overlap, Err(Error::DuplicateKey(key)) if key == "a"));
+    }
+
     #[test]
     fn test_clarity_tuple_display() {
         let addr = "ST3J2GVMMM2R07ZFBJDWTYEYAR8FZH5WKDTFJ9AHA";
@@ -1462,4 +2414,369 @@ mod tests {
             clarity!(StringUtf8, "hello \u{1234}")
         )
     }
+
+    #[test]
+    fn test_clarity_decode_length_mismatch() {
+        let cases = [
+            ("02ffffffff0102", 0xffff_ffff, 2),
+            ("0d0000000a616263", 10, 3),
+            ("0e00000004f09f", 4, 2),
+            ("0b0000000301", 3, 1),
+            ("0c0000000201", 2, 1),
+        ];
+
+        for (hex, declared, available) in cases {
+            let bytes = hex_to_bytes(hex).unwrap();
+            let err = decode_clarity_type(&bytes).unwrap_err();
+            assert!(
+                matches!(err, Error::LengthMismatch { declared: d, available: a } if d == declared && a == available),
+                "{hex}: {err:?}"
+            );
+        }
+
+        let truncated = hex_to_bytes("0b000000020100000000000000000000000000000001").unwrap();
+        assert!(matches!(
+            List::decode(&truncated),
+            Err(Error::LengthMismatch { .. })
+        ));
+
+        let tuple = hex_to_bytes("0c00000001056162").unwrap();
+        assert!(matches!(
+            Tuple::decode(&tuple),
+            Err(Error::LengthMismatch {
+                declared: 5,
+                available: 2
+            })
+        ));
+
+        assert!(matches!(
+            decode_clarity_type(&[0x02, 0x00]),
+            Err(Error::LengthMismatch {
+                declared: 4,
+                available: 1
+            })
+        ));
+        assert!(matches!(
+            FnArguments::decode(&hex_to_bytes("ffffffff00").unwrap()),
+            Err(Error::LengthMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_clarity_decode_empty() {
+        let results = [
+            Int::decode(&[]).map(|_| ()),
+            UInt::decode(&[]).map(|_| ()),
+            Buffer::decode(&[]).map(|_| ()),
+            True::decode(&[]).map(|_| ()),
+            False::decode(&[]).map(|_| ()),
+            PrincipalStandard::decode(&[]).map(|_| ()),
+            PrincipalContract::decode(&[]).map(|_| ()),
+            ResponseOk::decode(&[]).map(|_| ()),
+            ResponseErr::decode(&[]).map(|_| ()),
+            OptionalSome::decode(&[]).map(|_| ()),
+            OptionalNone::decode(&[]).map(|_| ()),
+            List::decode(&[]).map(|_| ()),
+            Tuple::decode(&[]).map(|_| ()),
+            StringAscii::decode(&[]).map(|_| ()),
+            StringUtf8::decode(&[]).map(|_| ()),
+            decode_clarity_type(&[]).map(|_| ()),
+        ];
+
+        for (i, result) in results.into_iter().enumerate() {
+            assert!(
+                matches!(result, Err(Error::LengthMismatch { available: 0, .. })),
+                "{i}: {result:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_clarity_path() {
+        use crate::clarity::clarity_path;
+
+        let value = clarity!(
+            Tuple,
+            (
+                "foo",
+                clarity!(
+                    Tuple,
+                    (
+                        "bar",
+                        clarity!(
+                            List,
+                            clarity!(Tuple, ("baz", clarity!(UInt, 1))),
+                            clarity!(Tuple, ("baz", clarity!(UInt, 2)))
+                        )
+                    )
+                )
+            )
+        );
+
+        let leaf = clarity_path(&value, "foo.bar.1.baz").unwrap();
+        assert_eq!(leaf.cast::<UInt>().unwrap().into_value(), 2);
+
+        let list = clarity_path(&value, "foo.bar").unwrap();
+        assert!(list.cast_as::<List>().is_ok());
+
+        for path in [
+            "foo.qux",
+            "foo.bar.2.baz",
+            "foo.bar.x",
+            "foo.bar.0.baz.0",
+            "",
+        ] {
+            assert!(clarity_path(&value, path).is_none(), "{path}");
+        }
+    }
+
+    #[test]
+    fn test_clarity_decoder() {
+        let mut bytes = clarity!(Int, -1).encode().unwrap();
+        bytes.extend(clarity!(StringAscii, "hello").encode().unwrap());
+        bytes.extend(
+            clarity!(List, clarity!(True), clarity!(UInt, 2))
+                .encode()
+                .unwrap(),
+        );
+
+        let values = ClarityDecoder::new(&bytes)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(values.len(), 3);
+        assert_eq!(values[0].to_string(), "-1");
+        assert_eq!(values[1].to_string(), "\"hello\"");
+        assert_eq!(values[2].to_string(), "(list true u2)");
+
+        assert_eq!(ClarityDecoder::new(&[]).count(), 0);
+
+        let mut decoder = ClarityDecoder::new(&[0x03, 0xff, 0x03]);
+        assert!(decoder.next().unwrap().is_ok());
+        assert!(matches!(
+            decoder.next(),
+            Some(Err(Error::UnexpectedType(0xff)))
+        ));
+        assert!(decoder.next().is_none());
+    }
+
+    #[test]
+    fn test_clarity_decode_lenient_unknown_type() {
+        let bytes = [0x42, 0xde, 0xad, 0xbe, 0xef];
+
+        assert!(matches!(
+            decode_clarity_type(&bytes),
+            Err(Error::UnexpectedType(0x42))
+        ));
+
+        let value = decode_clarity_type_lenient(&bytes).unwrap();
+        let unknown = value.cast_as::<UnknownClarity>().unwrap();
+        assert_eq!(unknown.tag(), 0x42);
+        assert_eq!(unknown.bytes(), &[0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(value.encode().unwrap(), bytes);
+        assert_eq!(value.to_string(), "0x42deadbeef");
+
+        let known = clarity!(UInt, 7).encode().unwrap();
+        let value = decode_clarity_type_lenient(&known).unwrap();
+        assert_eq!(value.cast::<UInt>().unwrap().into_value(), 7);
+        assert!(decode_clarity_type_lenient(&[]).is_err());
+
+        let mut nested = vec![List::id()];
+        nested.extend_from_slice(&2u32.to_be_bytes());
+        nested.extend_from_slice(&clarity!(UInt, 7).encode().unwrap());
+        nested.extend_from_slice(&[0x42, 0xde, 0xad]);
+        assert!(matches!(
+            decode_clarity_type_lenient(&nested),
+            Err(Error::UnexpectedType(0x42))
+        ));
+    }
+
+    #[test]
+    fn test_clarity_decode_huge_count() {
+        let mut list = vec![List::id()];
+        list.extend_from_slice(&u32::MAX.to_be_bytes());
+        list.extend_from_slice(&[CLARITY_TYPE_BOOL_TRUE; 8]);
+        assert!(matches!(
+            List::decode(&list),
+            Err(Error::LengthMismatch {
+                declared: 4_294_967_295,
+                available: 8
+            })
+        ));
+
+        let mut tuple = vec![Tuple::id()];
+        tuple.extend_from_slice(&u32::MAX.to_be_bytes());
+        tuple.extend_from_slice(&[0x01, b'a', CLARITY_TYPE_BOOL_TRUE]);
+        assert!(Tuple::decode(&tuple).is_err());
+
+        let mut args = u32::MAX.to_be_bytes().to_vec();
+        args.extend_from_slice(&[CLARITY_TYPE_BOOL_TRUE; 8]);
+        assert!(matches!(
+            FnArguments::decode(&args),
+            Err(Error::LengthMismatch {
+                declared: 4_294_967_295,
+                available: 8
+            })
+        ));
+
+        let mut args = 8u32.to_be_bytes().to_vec();
+        args.extend_from_slice(&[CLARITY_TYPE_BOOL_TRUE; 8]);
+        assert_eq!(FnArguments::decode(&args).unwrap().into_value().len(), 8);
+    }
+
+    #[test]
+    fn test_clarity_collection_count() {
+        let list = clarity!(List);
+        assert!(list.is_empty());
+        assert_eq!(list.count(), 0);
+        assert!(!Codec::is_empty(&list).unwrap());
+
+        let list = clarity!(List, clarity!(Int, 1), clarity!(Int, 2));
+        assert!(!list.is_empty());
+        assert_eq!(list.count(), 2);
+
+        let tuple = clarity!(Tuple);
+        assert!(tuple.is_empty());
+        assert_eq!(tuple.count(), 0);
+
+        let tuple = clarity!(Tuple, ("a", clarity!(Int, 1)));
+        assert!(!tuple.is_empty());
+        assert_eq!(tuple.count(), 1);
+
+        let args = clarity!(FnArguments);
+        assert!(args.is_empty());
+        assert_eq!(args.count(), 0);
+
+        let args = clarity!(
+            FnArguments,
+            clarity!(True),
+            clarity!(False),
+            clarity!(UInt, 3)
+        );
+        assert!(!args.is_empty());
+        assert_eq!(args.count(), 3);
+    }
+
+    #[test]
+    fn test_clarity_encode_into() {
+        let values: Vec<Box<dyn Clarity>> = vec![
+            Box::new(clarity!(Int, -7)),
+            Box::new(clarity!(Buffer, [0xde, 0xad])),
+            Box::new(clarity!(
+                ResponseErr,
+                clarity!(OptionalSome, clarity!(UInt, 1))
+            )),
+            Box::new(clarity!(
+                List,
+                clarity!(True),
+                clarity!(StringAscii, "abc"),
+                clarity!(Tuple, ("a", clarity!(UInt, 2)))
+            )),
+            Box::new(clarity!(
+                FnArguments,
+                clarity!(
+                    PrincipalStandard,
+                    "SP3FGQ8Z7JY9BWYZ5WM53E0M9NK7WHJF0691NZ159"
+                ),
+                clarity!(OptionalNone)
+            )),
+        ];
+
+        let mut out = vec![0xff];
+        let mut expected = vec![0xff];
+
+        for value in &values {
+            value.encode_into(&mut out).unwrap();
+            expected.extend_from_slice(&value.encode().unwrap());
+        }
+
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn test_clarity_value_size() {
+        let principal = "SP3FGQ8Z7JY9BWYZ5WM53E0M9NK7WHJF0691NZ159";
+        let cases: Vec<(Box<dyn Clarity>, usize)> = vec![
+            (Box::new(clarity!(Int, -1)), 16),
+            (Box::new(clarity!(True)), 1),
+            (Box::new(clarity!(OptionalNone)), 1),
+            (Box::new(clarity!(Buffer, [0x01, 0x02, 0x03])), 3),
+            (Box::new(clarity!(StringUtf8, "héllo")), 4 * 5),
+            (Box::new(clarity!(PrincipalStandard, principal)), 21),
+            (
+                Box::new(clarity!(PrincipalContract, principal, "token")),
+                21 + 1 + 5,
+            ),
+            (Box::new(clarity!(ResponseOk, clarity!(UInt, 1))), 1 + 16),
+        ];
+
+        for (value, size) in cases {
+            assert_eq!(value.size(), size, "{value}");
+        }
+
+        // 3 * (some + uint)
+        let list = clarity!(
+            List,
+            clarity!(OptionalSome, clarity!(UInt, 1)),
+            clarity!(OptionalSome, clarity!(UInt, 2)),
+            clarity!(OptionalSome, clarity!(UInt, 3))
+        );
+        assert_eq!(list.size(), 3 * (1 + 16));
+
+        // "id" + 16, "name" + 5, "meta" + ("ok" + 1, "tags" + (3 + 2))
+        let tuple = clarity!(
+            Tuple,
+            ("id", clarity!(UInt, 7)),
+            ("name", clarity!(StringAscii, "stack")),
+            (
+                "meta",
+                clarity!(
+                    Tuple,
+                    ("ok", clarity!(True)),
+                    (
+                        "tags",
+                        clarity!(
+                            List,
+                            clarity!(StringAscii, "abc"),
+                            clarity!(StringAscii, "de")
+                        )
+                    )
+                )
+            )
+        );
+        assert_eq!(
+            tuple.size(),
+            (2 + 16) + (4 + 5) + (4 + (2 + 1) + (4 + (3 + 2)))
+        );
+    }
+
+    #[test]
+    fn test_clarity_tuple_from_map() {
+        let mut map = BTreeMap::new();
+        map.insert("c".to_string(), clarity!(@box clarity!(UInt, 3)));
+        map.insert("a".to_string(), clarity!(@box clarity!(Int, 1)));
+        map.insert("b".to_string(), clarity!(@box clarity!(True)));
+
+        let tuple = Tuple::from(map.clone());
+        assert_eq!(tuple.keys().collect::<Vec<_>>(), ["a", "b", "c"]);
+        assert_eq!(tuple.to_string(), "(tuple (a 1) (b true) (c u3))");
+
+        let hashed = map
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect::<HashMap<_, _>>();
+        assert_eq!(
+            Tuple::from(hashed).encode().unwrap(),
+            tuple.encode().unwrap()
+        );
+
+        let roundtrip = tuple.into_map();
+        assert_eq!(
+            roundtrip.keys().collect::<Vec<_>>(),
+            map.keys().collect::<Vec<_>>()
+        );
+
+        for (key, value) in &map {
+            assert_eq!(roundtrip[key].encode().unwrap(), value.encode().unwrap());
+        }
+    }
 }
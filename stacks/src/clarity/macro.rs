@@ -13,7 +13,7 @@ macro_rules! clarity {
     (Int, $x:expr) => ($crate::clarity::Int::new($x));
     (UInt, $x:expr) => ($crate::clarity::UInt::new($x));
     (Buffer, $x:expr) => ($crate::clarity::Buffer::new($x.to_vec()));
-    (Buffer) => (@empty, Buffer);
+    (Buffer) => (clarity!(@empty, Buffer));
     (True) => ($crate::clarity::True::new());
     (False) => ($crate::clarity::False::new());
     (PrincipalStandard, $x:expr) => ($crate::clarity::PrincipalStandard::new($x.to_string()));
@@ -86,7 +86,6 @@ macro_rules! impl_clarity_primitive {
                 $id
             }
         }
-        impl $crate::clarity::Clarity for $name {}
     };
 }
 
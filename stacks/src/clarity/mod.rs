@@ -35,12 +35,55 @@ pub enum Error {
     /// Downcasting trait object to a concrete type failed.
     #[error("Bad downcast, please check the type identifier and the cast type")]
     BadDowncast,
+    /// Looked up a key that is not present in a tuple.
+    #[error("Key not found - received: {0}")]
+    KeyNotFound(String),
+    /// Inserted a key that is already present in a tuple.
+    #[error("Duplicate key - received: {0}")]
+    DuplicateKey(String),
+    /// Received an argument that is not a parameter of the function.
+    #[error("Unknown argument - received: {0}")]
+    UnknownArgument(String),
+    /// A parameter of the function was not supplied.
+    #[error("Missing argument - expected: {0}")]
+    MissingArgument(String),
+    /// Combined positional & named arguments in one contract-call.
+    #[error("Mixed arguments - positional and named arguments can't be combined")]
+    MixedArguments,
+    /// Looked up an index that is out of bounds.
+    #[error("Index out of bounds - received: {0}, length: {1}")]
+    IndexOutOfBounds(usize, usize),
+    /// The input ended before a fixed-size field could be read.
+    #[error("Unexpected end of input - expected: {expected} bytes, available: {available} bytes")]
+    UnexpectedEnd {
+        /// The size of the field.
+        expected: usize,
+        /// The number of bytes remaining in the input.
+        available: usize,
+    },
+    /// A length prefix declared more bytes than the input contains.
+    #[error("Length mismatch - declared: {declared} bytes, available: {available} bytes")]
+    LengthMismatch {
+        /// The length declared by the prefix.
+        declared: usize,
+        /// The number of bytes remaining in the input.
+        available: usize,
+    },
     /// Decoding a type with an unknown type identifier.
     #[error("Unexpected type identifier - received: {0}")]
     UnexpectedType(u8),
+    /// Received an invalid contract name.
+    #[error("Bad contract name - received: {0}")]
+    BadContractName(String),
+    /// Received an invalid function name.
+    #[error("Bad function name - received: {0}")]
+    BadFunctionName(String),
     /// `crypto::c32` crate errors.
     #[error(transparent)]
     C32(#[from] crypto::c32::Error),
+    /// `crypto::hex` crate errors.
+    #[error(transparent)]
+    Hex(#[from] crypto::hex::Error),
     /// Conversion from a integer failed.
     #[error(transparent)]
     TryFromInt(#[from] std::num::TryFromIntError),
@@ -85,14 +128,70 @@ pub(crate) const CLARITY_TYPE_STRING_UTF8: u8 = 0x0e;
 /// The clarity type identifier for non-standard types.
 pub(crate) const CLARITY_TYPE_NON_STD: u8 = 0xff;
 
+/// The encoded size of the smallest clarity value, e.g. `true` or `none`.
+pub(crate) const CLARITY_MIN_VALUE_SIZE: usize = 1;
+
+/// The value size of a standard principal, a version byte & a 20-byte hash.
+pub(crate) const CLARITY_STD_PR_SIZE: usize = 21;
+
+/// The value size added by an optional or response wrapper.
+pub(crate) const CLARITY_WRAPPER_SIZE: usize = 1;
+
 /// Trait for Clarity types.
-pub trait Clarity: Codec + Ident + Any + DynClone + Send + Sync + Display + Debug {}
+pub trait Clarity: Codec + Ident + Any + DynClone + Send + Sync + Display + Debug {
+    /// Returns the size of the value under the clarity cost rules.
+    ///
+    /// This is not the encoded length: an `Int` is 16 bytes, `true` & `none` are 1 byte,
+    /// `some`/`ok`/`err` add 1 byte to their inner value, a `StringUtf8` counts 4 bytes
+    /// per character, a `List` sums its element sizes & a `Tuple` sums its key lengths
+    /// & member sizes.
+    fn size(&self) -> usize;
+
+    /// Compares two clarity values by their encoded bytes.
+    ///
+    /// Values that fail to encode are equal if they have the same type & display
+    /// the same, so that every value is equal to itself.
+    fn clarity_eq(&self, other: &dyn Clarity) -> bool {
+        match (self.encode(), other.encode()) {
+            (Ok(a), Ok(b)) => a == b,
+            (Err(_), Err(_)) => {
+                self.as_any().type_id() == other.as_any().type_id()
+                    && self.to_string() == other.to_string()
+            }
+            _ => false,
+        }
+    }
+}
 clone_trait_object!(Clarity);
 
+impl PartialEq for dyn Clarity {
+    fn eq(&self, other: &Self) -> bool {
+        self.clarity_eq(other)
+    }
+}
+
+impl Eq for dyn Clarity {}
+
+// Works around rust-lang/rust#31740, without it `assert_eq!` on two
+// `Box<dyn Clarity>` values attempts to move out of the borrowed boxes.
+impl PartialEq<&Self> for Box<dyn Clarity> {
+    fn eq(&self, other: &&Self) -> bool {
+        self.as_ref().clarity_eq(other.as_ref())
+    }
+}
+
 /// Trait for encoding/decoding consensus data.
 pub trait Codec {
     /// Encodes the consensus type into bytes.
     fn encode(&self) -> Result<Vec<u8>, Error>;
+    /// Encodes the consensus type by appending to `out`, e.g. to reuse one buffer.
+    ///
+    /// Container types write their members directly into `out`. On error, `out`
+    /// may contain a partial encoding.
+    fn encode_into(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        out.extend_from_slice(&self.encode()?);
+        Ok(())
+    }
     /// Decodes the consensus data into a clarity type.
     fn decode(bytes: &[u8]) -> Result<Self, Error>
     where
@@ -170,9 +269,14 @@ impl_clarity_primitive!(StringUtf8, String, CLARITY_TYPE_STRING_UTF8);
 impl_clarity_primitive!(LengthPrefixedStr, String, CLARITY_TYPE_NON_STD);
 impl_clarity_primitive!(FnArguments, Vec<Box<dyn Clarity>>, CLARITY_TYPE_NON_STD);
 
+impl_clarity_primitive!(UnknownClarity, (u8, Vec<u8>), CLARITY_TYPE_NON_STD);
+
 /// Decodes a Clarity type from encoded bytes.
 pub fn decode_clarity_type(bytes: &[u8]) -> Result<Box<dyn Clarity>, Error> {
-    let tag = bytes[0];
+    let tag = *bytes.first().ok_or(Error::LengthMismatch {
+        declared: 1,
+        available: 0,
+    })?;
 
     match tag {
         CLARITY_TYPE_INT => Ok(Box::new(Int::decode(bytes)?)),
@@ -193,3 +297,84 @@ pub fn decode_clarity_type(bytes: &[u8]) -> Result<Box<dyn Clarity>, Error> {
         _ => Err(Error::UnexpectedType(tag)),
     }
 }
+
+/// Decodes a Clarity type from encoded bytes, tolerating unknown type identifiers.
+///
+/// Unlike `decode_clarity_type`, an unrecognized top-level type identifier does not
+/// fail, the remaining bytes are instead kept verbatim in an `UnknownClarity` value.
+/// Unknown type identifiers nested in a known container are still an error.
+pub fn decode_clarity_type_lenient(bytes: &[u8]) -> Result<Box<dyn Clarity>, Error> {
+    match decode_clarity_type(bytes) {
+        Err(Error::UnexpectedType(tag)) if bytes.first() == Some(&tag) => {
+            Ok(Box::new(UnknownClarity::decode(bytes)?))
+        }
+        result => result,
+    }
+}
+
+/// An iterator decoding consecutive Clarity values from a byte buffer.
+///
+/// Stops at the end of the buffer, or after yielding the first error.
+#[derive(Debug, Clone)]
+pub struct ClarityDecoder<'a> {
+    /// The remaining bytes to decode.
+    bytes: &'a [u8],
+    /// Decoding failed & the iterator is exhausted.
+    failed: bool,
+}
+
+impl<'a> ClarityDecoder<'a> {
+    /// Creates a new `ClarityDecoder`.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            failed: false,
+        }
+    }
+}
+
+impl Iterator for ClarityDecoder<'_> {
+    type Item = Result<Box<dyn Clarity>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.bytes.is_empty() {
+            return None;
+        }
+
+        let result = decode_clarity_type(self.bytes).and_then(|value| {
+            let len = value.len()?;
+            self.bytes = self.bytes.get(len..).unwrap_or_default();
+            Ok(value)
+        });
+
+        self.failed = result.is_err();
+        Some(result)
+    }
+}
+
+/// Looks up a nested value by a dot-separated path, e.g. `"foo.bar.0.baz"`.
+///
+/// Numeric segments index into lists, all other segments look up tuple keys.
+/// Returns `None` if a segment does not resolve.
+pub fn clarity_path(value: &dyn Clarity, path: &str) -> Option<Box<dyn Clarity>> {
+    let mut current = value;
+
+    for segment in path.split('.') {
+        let any = current.as_any();
+
+        current = if let Some(tuple) = any.downcast_ref::<Tuple>() {
+            tuple
+                .value()
+                .iter()
+                .find(|(key, _)| key == segment)
+                .map(|(_, value)| value.as_ref())?
+        } else if let Some(list) = any.downcast_ref::<List>() {
+            let index = segment.parse::<usize>().ok()?;
+            list.value().get(index)?.as_ref()
+        } else {
+            return None;
+        };
+    }
+
+    Some(dyn_clone::clone_box(current))
+}
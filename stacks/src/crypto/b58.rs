@@ -36,6 +36,9 @@ pub enum Error {
     /// Expected and received checksums are different.
     #[error("Bad checksum - expected {0}, received: {1}")]
     BadChecksum(String, String),
+    /// The decoded input is too short to hold a version byte & checksum.
+    #[error("Input too short, received {0} bytes - expected at least 5")]
+    TooShort(usize),
     /// Received an unknown bitcoin version byte.
     #[error("Unknown address version, received: {0}")]
     UnknownAddressVersion(u8),
@@ -45,7 +48,7 @@ pub enum Error {
 }
 
 /// The B58 address version.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Version {
     /// The mainnet P2PKH address version.
     MainnetP2PKH = 0,
@@ -57,6 +60,20 @@ pub enum Version {
     TestnetP2SH = 196,
 }
 
+impl TryFrom<u8> for Version {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::MainnetP2PKH),
+            5 => Ok(Self::MainnetP2SH),
+            111 => Ok(Self::TestnetP2PKH),
+            196 => Ok(Self::TestnetP2SH),
+            _ => Err(Error::UnknownAddressVersion(value)),
+        }
+    }
+}
+
 /// Encode a byte slice into a `Base58` string.
 pub fn b58_encode<T>(slice: T) -> String
 where
@@ -175,6 +192,10 @@ where
     let buff = b58_decode(str)?;
     let buff_max = buff.len();
 
+    if buff_max < 5 {
+        return Err(Error::TooShort(buff_max));
+    }
+
     let checksum = &buff[buff_max - 4..];
     let data = buff[1..buff_max - 4].to_vec();
 
@@ -297,4 +318,12 @@ mod tests {
             assert_eq!(decoded, Err(Error::BadChar(c)));
         }
     }
+
+    #[test]
+    fn test_crypto_b58_check_too_short() {
+        assert_eq!(base58check_decode(""), Err(Error::TooShort(0)));
+        assert_eq!(base58check_decode("1"), Err(Error::TooShort(1)));
+        assert_eq!(base58check_decode("abc"), Err(Error::TooShort(3)));
+        assert_eq!(base58check_decode("1111"), Err(Error::TooShort(4)));
+    }
 }
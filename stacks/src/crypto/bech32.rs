@@ -0,0 +1,493 @@
+// © 2024 Max Karou. All Rights Reserved.
+// Licensed under Apache Version 2.0, or MIT License, at your discretion.
+//
+// Apache License: http://www.apache.org/licenses/LICENSE-2.0
+// MIT License: http://opensource.org/licenses/MIT
+//
+// Usage of this file is permitted solely under a sanctioned license.
+
+use crate::crypto::b58;
+use crate::crypto::base58check_decode;
+use crate::crypto::base58check_encode;
+use crate::crypto::hash::HASH160_ENCODED_SIZE;
+
+/// `Bech32` alphabet, used for encoding/decoding.
+pub(crate) const BECH32_ALPHABET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// The generator coefficients of the `Bech32` checksum.
+pub(crate) const BECH32_GENERATOR: [u32; 5] = [
+    0x3b6a_57b2,
+    0x2650_8e6d,
+    0x1ea1_19fa,
+    0x3d42_33dd,
+    0x2a14_62b3,
+];
+
+/// The checksum constant of `Bech32` (BIP-173), used for witness version 0.
+pub(crate) const BECH32_CONST: u32 = 0x0000_0001;
+/// The checksum constant of `Bech32m` (BIP-350), used for witness version 1+.
+pub(crate) const BECH32M_CONST: u32 = 0x2bc8_30a3;
+
+/// The length of the checksum in 5-bit groups.
+pub(crate) const BECH32_CHECKSUM_LEN: usize = 6;
+/// The maximum length of an encoded segwit address.
+pub(crate) const BECH32_MAX_LEN: usize = 90;
+
+/// The human-readable part of mainnet segwit addresses.
+pub const BECH32_HRP_MAINNET: &str = "bc";
+/// The human-readable part of testnet segwit addresses.
+pub const BECH32_HRP_TESTNET: &str = "tb";
+/// The human-readable part of regtest segwit addresses.
+pub const BECH32_HRP_REGTEST: &str = "bcrt";
+
+/// The `PoX` address version for P2PKH.
+pub(crate) const POX_ADDRESS_P2PKH: u8 = 0x00;
+/// The `PoX` address version for P2SH.
+pub(crate) const POX_ADDRESS_P2SH: u8 = 0x01;
+/// The `PoX` address version for P2WPKH.
+pub(crate) const POX_ADDRESS_P2WPKH: u8 = 0x04;
+/// The `PoX` address version for P2WSH.
+pub(crate) const POX_ADDRESS_P2WSH: u8 = 0x05;
+/// The `PoX` address version for P2TR.
+pub(crate) const POX_ADDRESS_P2TR: u8 = 0x06;
+
+/// Error variants for `Bech32` encoding/decoding.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum Error {
+    /// Received a character that is not in the `Bech32` alphabet.
+    #[error("Bad character encountered: {0}")]
+    BadChar(char),
+    /// Received a string mixing upper & lower case characters.
+    #[error("Bad input string, must not mix upper & lower case characters")]
+    MixedCase,
+    /// Expected and received checksums are different.
+    #[error("Bad checksum")]
+    BadChecksum,
+    /// Received a string of an invalid length or without a separator.
+    #[error("Bad input length: {0}")]
+    BadLength(usize),
+    /// Received an unknown human-readable part.
+    #[error("Unknown human-readable part, received: {0} - expected one of '[bc, tb, bcrt]'")]
+    UnknownHrp(String),
+    /// Received a witness version above 16.
+    #[error("Bad witness version, received: {0}")]
+    BadWitnessVersion(u8),
+    /// Received a witness program of an invalid length for its version.
+    #[error("Bad witness program length, received: {0} bytes")]
+    BadProgramLength(usize),
+    /// Received non-zero or excess padding bits.
+    #[error("Bad padding")]
+    BadPadding,
+    /// The address has no `PoX` address equivalent.
+    #[error("Unsupported PoX address: {0}")]
+    UnsupportedPoxAddress(String),
+    /// `crypto::b58` crate errors.
+    #[error(transparent)]
+    Base58(#[from] b58::Error),
+}
+
+/// A bitcoin address, either `Base58Check` (legacy) or `Bech32` (segwit) encoded.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BitcoinAddress {
+    /// A legacy P2PKH or P2SH address.
+    Base58 {
+        /// The address version.
+        version: b58::Version,
+        /// The public key or script hash.
+        hash: [u8; HASH160_ENCODED_SIZE],
+    },
+    /// A native segwit address.
+    Segwit {
+        /// The human-readable part.
+        hrp: String,
+        /// The witness version.
+        version: u8,
+        /// The witness program.
+        program: Vec<u8>,
+    },
+}
+
+impl BitcoinAddress {
+    /// Returns `true` if the address belongs to the bitcoin mainnet.
+    pub fn is_mainnet(&self) -> bool {
+        match self {
+            Self::Base58 { version, .. } => {
+                matches!(
+                    version,
+                    b58::Version::MainnetP2PKH | b58::Version::MainnetP2SH
+                )
+            }
+            Self::Segwit { hrp, .. } => hrp == BECH32_HRP_MAINNET,
+        }
+    }
+
+    /// Returns the `PoX` address `{version, hashbytes}` of the address.
+    pub fn pox_address(&self) -> Result<(u8, Vec<u8>), Error> {
+        let version = match self {
+            Self::Base58 { version, .. } => match version {
+                b58::Version::MainnetP2PKH | b58::Version::TestnetP2PKH => POX_ADDRESS_P2PKH,
+                b58::Version::MainnetP2SH | b58::Version::TestnetP2SH => POX_ADDRESS_P2SH,
+            },
+            Self::Segwit {
+                version, program, ..
+            } => match (version, program.len()) {
+                (0, 20) => POX_ADDRESS_P2WPKH,
+                (0, 32) => POX_ADDRESS_P2WSH,
+                (1, 32) => POX_ADDRESS_P2TR,
+                _ => return Err(Error::UnsupportedPoxAddress(self.to_string())),
+            },
+        };
+
+        Ok((version, self.hashbytes().to_vec()))
+    }
+
+    /// Returns the hash or witness program of the address.
+    pub fn hashbytes(&self) -> &[u8] {
+        match self {
+            Self::Base58 { hash, .. } => hash,
+            Self::Segwit { program, .. } => program,
+        }
+    }
+}
+
+impl std::fmt::Display for BitcoinAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Base58 { version, hash } => {
+                write!(f, "{}", base58check_encode(hash, *version as u8))
+            }
+            Self::Segwit {
+                hrp,
+                version,
+                program,
+            } => {
+                let encoded = bech32_encode(hrp, *version, program).map_err(|_| std::fmt::Error)?;
+                write!(f, "{encoded}")
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for BitcoinAddress {
+    type Err = Error;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        let lower = str.to_ascii_lowercase();
+        let is_segwit = [BECH32_HRP_MAINNET, BECH32_HRP_TESTNET, BECH32_HRP_REGTEST]
+            .iter()
+            .any(|hrp| lower.starts_with(&format!("{hrp}1")));
+
+        if is_segwit {
+            let (hrp, version, program) = bech32_decode(str)?;
+            return Ok(Self::Segwit {
+                hrp,
+                version,
+                program,
+            });
+        }
+
+        let (hash, version) = base58check_decode(str)?;
+        let version = b58::Version::try_from(version)?;
+        let hash = hash
+            .try_into()
+            .map_err(|hash: Vec<u8>| Error::BadProgramLength(hash.len()))?;
+
+        Ok(Self::Base58 { version, hash })
+    }
+}
+
+/// Computes the `Bech32` checksum polynomial of a sequence of 5-bit values.
+fn polymod(values: &[u8]) -> u32 {
+    let mut chk = 1u32;
+
+    for value in values {
+        let top = chk >> 25;
+        chk = ((chk & 0x01ff_ffff) << 5) ^ u32::from(*value);
+
+        for (i, generator) in BECH32_GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= generator;
+            }
+        }
+    }
+
+    chk
+}
+
+/// Expands the human-readable part for checksum computation.
+fn hrp_expand(hrp: &str) -> Vec<u8> {
+    let mut buff = hrp.bytes().map(|b| b >> 5).collect::<Vec<_>>();
+    buff.push(0);
+    buff.extend(hrp.bytes().map(|b| b & 0x1f));
+    buff
+}
+
+/// Regroups a sequence of `from`-bit values into `to`-bit values.
+fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Result<Vec<u8>, Error> {
+    let mut acc = 0u32;
+    let mut bits = 0u32;
+    let mut buff = vec![];
+    let max = (1u32 << to) - 1;
+
+    for value in data {
+        acc = (acc << from) | u32::from(*value);
+        bits += from;
+
+        while bits >= to {
+            bits -= to;
+            buff.push(u8::try_from((acc >> bits) & max).map_err(|_| Error::BadPadding)?);
+        }
+    }
+
+    if pad {
+        if bits > 0 {
+            buff.push(u8::try_from((acc << (to - bits)) & max).map_err(|_| Error::BadPadding)?);
+        }
+    } else if bits >= from || (acc << (to - bits)) & max != 0 {
+        return Err(Error::BadPadding);
+    }
+
+    Ok(buff)
+}
+
+/// Validates the witness version & program length of a segwit address.
+fn validate_witness(version: u8, program: &[u8]) -> Result<(), Error> {
+    if version > 16 {
+        return Err(Error::BadWitnessVersion(version));
+    }
+
+    if !(2..=40).contains(&program.len()) || (version == 0 && ![20, 32].contains(&program.len())) {
+        return Err(Error::BadProgramLength(program.len()));
+    }
+
+    Ok(())
+}
+
+/// Encode a witness version & program into a segwit address.
+///
+/// Witness version 0 uses `Bech32`, witness version 1+ uses `Bech32m`.
+pub fn bech32_encode(hrp: &str, version: u8, program: &[u8]) -> Result<String, Error> {
+    validate_witness(version, program)?;
+
+    let hrp = hrp.to_ascii_lowercase();
+    let mut data = vec![version];
+    data.extend(convert_bits(program, 8, 5, true)?);
+
+    let constant = if version == 0 {
+        BECH32_CONST
+    } else {
+        BECH32M_CONST
+    };
+
+    let mut values = hrp_expand(&hrp);
+    values.extend_from_slice(&data);
+    values.extend_from_slice(&[0; BECH32_CHECKSUM_LEN]);
+    let checksum = polymod(&values) ^ constant;
+
+    for i in 0..BECH32_CHECKSUM_LEN {
+        data.push(((checksum >> (5 * (5 - i))) & 0x1f) as u8);
+    }
+
+    let mut encoded = hrp;
+    encoded.push('1');
+    encoded.extend(data.iter().map(|d| BECH32_ALPHABET[*d as usize] as char));
+
+    Ok(encoded)
+}
+
+/// Decode a segwit address into its human-readable part, witness version & program.
+pub fn bech32_decode(str: &str) -> Result<(String, u8, Vec<u8>), Error> {
+    if str.len() > BECH32_MAX_LEN {
+        return Err(Error::BadLength(str.len()));
+    }
+
+    if str.chars().any(|c| c.is_ascii_lowercase()) && str.chars().any(|c| c.is_ascii_uppercase()) {
+        return Err(Error::MixedCase);
+    }
+
+    let str = str.to_ascii_lowercase();
+    let (hrp, data) = str.rsplit_once('1').ok_or(Error::BadLength(str.len()))?;
+
+    if hrp.is_empty() || data.len() < BECH32_CHECKSUM_LEN + 1 {
+        return Err(Error::BadLength(str.len()));
+    }
+
+    if ![BECH32_HRP_MAINNET, BECH32_HRP_TESTNET, BECH32_HRP_REGTEST].contains(&hrp) {
+        return Err(Error::UnknownHrp(hrp.to_string()));
+    }
+
+    let data = data
+        .chars()
+        .map(|c| {
+            BECH32_ALPHABET
+                .iter()
+                .position(|a| *a as char == c)
+                .and_then(|i| u8::try_from(i).ok())
+                .ok_or(Error::BadChar(c))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let version = data[0];
+    let constant = if version == 0 {
+        BECH32_CONST
+    } else {
+        BECH32M_CONST
+    };
+
+    let mut values = hrp_expand(hrp);
+    values.extend_from_slice(&data);
+
+    if polymod(&values) != constant {
+        return Err(Error::BadChecksum);
+    }
+
+    let program = convert_bits(&data[1..data.len() - BECH32_CHECKSUM_LEN], 5, 8, false)?;
+    validate_witness(version, &program)?;
+
+    Ok((hrp.to_string(), version, program))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::hex::bytes_to_hex;
+
+    #[test]
+    fn test_crypto_bech32_vectors() {
+        let cases = [
+            (
+                "BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4",
+                "bc",
+                0,
+                "751e76e8199196d454941c45d1b3a323f1433bd6",
+            ),
+            (
+                "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+                "tb",
+                0,
+                "1863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262",
+            ),
+            (
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+                "bc",
+                1,
+                "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            ),
+        ];
+
+        for (address, exp_hrp, exp_version, exp_program) in cases {
+            let (hrp, version, program) = bech32_decode(address).unwrap();
+            assert_eq!(hrp, exp_hrp);
+            assert_eq!(version, exp_version);
+            assert_eq!(bytes_to_hex(&program), exp_program);
+
+            let encoded = bech32_encode(&hrp, version, &program).unwrap();
+            assert_eq!(encoded, address.to_ascii_lowercase());
+        }
+    }
+
+    #[test]
+    fn test_crypto_bech32_invalid() {
+        // `Bech32m` checksum on a witness version 0 program.
+        assert_eq!(
+            bech32_decode("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kemeawh"),
+            Err(Error::BadChecksum)
+        );
+        // `Bech32` checksum on a witness version 1 program.
+        assert_eq!(
+            bech32_decode("bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqh2y7hd"),
+            Err(Error::BadChecksum)
+        );
+        assert_eq!(
+            bech32_decode("tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3Q0sl5k7"),
+            Err(Error::MixedCase)
+        );
+        assert_eq!(
+            bech32_decode("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3tb"),
+            Err(Error::BadChar('b'))
+        );
+        assert!(matches!(
+            bech32_decode("ltc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"),
+            Err(Error::UnknownHrp(_))
+        ));
+        assert_eq!(
+            bech32_encode("bc", 0, &[0; 21]),
+            Err(Error::BadProgramLength(21))
+        );
+        assert_eq!(
+            bech32_encode("bc", 17, &[0; 32]),
+            Err(Error::BadWitnessVersion(17))
+        );
+    }
+
+    #[test]
+    fn test_crypto_bech32_bitcoin_address_pox() {
+        let cases = [
+            (
+                "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+                POX_ADDRESS_P2WPKH,
+                "751e76e8199196d454941c45d1b3a323f1433bd6",
+                true,
+            ),
+            (
+                "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+                POX_ADDRESS_P2WSH,
+                "1863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262",
+                false,
+            ),
+            (
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+                POX_ADDRESS_P2TR,
+                "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+                true,
+            ),
+        ];
+
+        for (str, exp_version, exp_hash, mainnet) in cases {
+            let address = str.parse::<BitcoinAddress>().unwrap();
+            let (version, hashbytes) = address.pox_address().unwrap();
+
+            assert_eq!(version, exp_version);
+            assert_eq!(bytes_to_hex(&hashbytes), exp_hash);
+            assert_eq!(address.is_mainnet(), mainnet);
+            assert_eq!(address.to_string(), str);
+        }
+
+        let hash = [0xab; HASH160_ENCODED_SIZE];
+        let legacy = [
+            (b58::Version::MainnetP2PKH, POX_ADDRESS_P2PKH, true),
+            (b58::Version::MainnetP2SH, POX_ADDRESS_P2SH, true),
+            (b58::Version::TestnetP2PKH, POX_ADDRESS_P2PKH, false),
+            (b58::Version::TestnetP2SH, POX_ADDRESS_P2SH, false),
+        ];
+
+        for (b58_version, exp_version, mainnet) in legacy {
+            let str = base58check_encode(hash, b58_version as u8);
+            let address = str.parse::<BitcoinAddress>().unwrap();
+
+            assert_eq!(
+                address,
+                BitcoinAddress::Base58 {
+                    version: b58_version,
+                    hash
+                }
+            );
+            assert_eq!(address.pox_address().unwrap(), (exp_version, hash.to_vec()));
+            assert_eq!(address.is_mainnet(), mainnet);
+            assert_eq!(address.to_string(), str);
+        }
+
+        let unknown = base58check_encode(hash, 0x30);
+        assert_eq!(
+            unknown.parse::<BitcoinAddress>(),
+            Err(Error::Base58(b58::Error::UnknownAddressVersion(0x30)))
+        );
+
+        let future = bech32_encode("bc", 2, &[0; 16]).unwrap();
+        let address = future.parse::<BitcoinAddress>().unwrap();
+        assert!(matches!(
+            address.pox_address(),
+            Err(Error::UnsupportedPoxAddress(_))
+        ));
+    }
+}
@@ -8,14 +8,26 @@
 
 use secp256k1::PublicKey;
 
+use crate::crypto::hash::HASH160_ENCODED_SIZE;
 use crate::crypto::DSha256Hash;
 use crate::crypto::Hash160;
+#[cfg(feature = "transaction")]
+use crate::crypto::MessageSignature;
 use crate::crypto::Sha256Hash;
+#[cfg(feature = "transaction")]
+use crate::transaction::TransactionVersion;
 
 /// `C32` alphabet, used for encoding/decoding.
 pub(crate) const C32_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
 
+/// The mainnet burn address, a `P2PKH` address with an all-zero hash.
+pub const BURN_ADDRESS_MAINNET: &str = "SP000000000000000000002Q6VF78";
+/// The testnet burn address, a `P2PKH` address with an all-zero hash.
+pub const BURN_ADDRESS_TESTNET: &str = "ST000000000000000000002AMW42H";
+
 /// `C32` byte map, used for lookup of values.
+///
+/// Lowercase letters decode like uppercase, `O` decodes as `0` & `I`/`L` as `1`.
 pub(crate) const C32_BYTE_MAP: [i8; 128] = [
     -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
     -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
@@ -43,6 +55,15 @@ pub enum Error {
     /// Received an unknown version byte.
     #[error("Unknown address version, received: {0} - expected one of '[22, 26, 20, 21]'")]
     UnknownAddressVersion(u8),
+    /// Received an unknown hash-mode name.
+    #[error("Unknown hash-mode, received: {0} - expected one of '[p2pkh, p2sh, p2wpkh, p2wsh]'")]
+    UnknownMode(String),
+    /// Received an unknown address version name.
+    #[error("Unknown address version, received: {0} - expected one of '[mainnet-p2pkh, mainnet-p2sh, testnet-p2pkh, testnet-p2sh]'")]
+    UnknownVersion(String),
+    /// `crypto::hash` crate errors.
+    #[error(transparent)]
+    Hash(#[from] crate::crypto::hash::Error),
     /// Conversion from a integer failed.
     #[error(transparent)]
     TryFromInt(#[from] std::num::TryFromIntError),
@@ -65,6 +86,73 @@ pub enum Mode {
     P2WSH = 0x03,
 }
 
+impl Mode {
+    /// Returns the hash-mode for an address version byte.
+    ///
+    /// Single-sig versions map to `P2PKH`, multi-sig versions map to `P2SH`.
+    pub fn from_version(version: u8) -> Result<Self, Error> {
+        match version {
+            22 | 26 => Ok(Self::P2PKH),
+            20 | 21 => Ok(Self::P2SH),
+            _ => Err(Error::UnknownAddressVersion(version)),
+        }
+    }
+}
+
+/// The hash-mode of a single-sig spending condition, `P2PKH` or `P2WPKH`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SingleSigMode {
+    /// The hash-mode type for P2PKH.
+    P2PKH,
+    /// The hash-mode type for P2WPKH.
+    P2WPKH,
+}
+
+impl From<SingleSigMode> for Mode {
+    fn from(mode: SingleSigMode) -> Self {
+        match mode {
+            SingleSigMode::P2PKH => Self::P2PKH,
+            SingleSigMode::P2WPKH => Self::P2WPKH,
+        }
+    }
+}
+
+impl std::fmt::Display for Mode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::P2PKH => write!(f, "p2pkh"),
+            Self::P2SH => write!(f, "p2sh"),
+            Self::P2WPKH => write!(f, "p2wpkh"),
+            Self::P2WSH => write!(f, "p2wsh"),
+        }
+    }
+}
+
+impl std::str::FromStr for Mode {
+    type Err = Error;
+
+    /// Parses a hash-mode case-insensitively.
+    ///
+    /// Also accepts the API spellings of the segwit modes, `p2wpkh-p2sh` & `p2wsh-p2sh`.
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        match str.to_ascii_lowercase().as_str() {
+            "p2pkh" => Ok(Self::P2PKH),
+            "p2sh" => Ok(Self::P2SH),
+            "p2wpkh" | "p2wpkh-p2sh" => Ok(Self::P2WPKH),
+            "p2wsh" | "p2wsh-p2sh" => Ok(Self::P2WSH),
+            _ => Err(Error::UnknownMode(str.to_string())),
+        }
+    }
+}
+
+impl TryFrom<&str> for Mode {
+    type Error = Error;
+
+    fn try_from(str: &str) -> Result<Self, Self::Error> {
+        str.parse()
+    }
+}
+
 /// The C32 address version.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Version {
@@ -78,6 +166,67 @@ pub enum Version {
     TestnetP2SH = 21,
 }
 
+impl TryFrom<u8> for Version {
+    type Error = Error;
+
+    fn try_from(version: u8) -> Result<Self, Self::Error> {
+        match version {
+            22 => Ok(Self::MainnetP2PKH),
+            20 => Ok(Self::MainnetP2SH),
+            26 => Ok(Self::TestnetP2PKH),
+            21 => Ok(Self::TestnetP2SH),
+            _ => Err(Error::UnknownAddressVersion(version)),
+        }
+    }
+}
+
+impl Version {
+    /// Returns `true` if the address version belongs to mainnet.
+    pub fn is_mainnet(&self) -> bool {
+        matches!(self, Self::MainnetP2PKH | Self::MainnetP2SH)
+    }
+}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MainnetP2PKH => write!(f, "mainnet-p2pkh"),
+            Self::MainnetP2SH => write!(f, "mainnet-p2sh"),
+            Self::TestnetP2PKH => write!(f, "testnet-p2pkh"),
+            Self::TestnetP2SH => write!(f, "testnet-p2sh"),
+        }
+    }
+}
+
+impl std::str::FromStr for Version {
+    type Err = Error;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        match str.to_ascii_lowercase().as_str() {
+            "mainnet-p2pkh" => Ok(Self::MainnetP2PKH),
+            "mainnet-p2sh" => Ok(Self::MainnetP2SH),
+            "testnet-p2pkh" => Ok(Self::TestnetP2PKH),
+            "testnet-p2sh" => Ok(Self::TestnetP2SH),
+            _ => Err(Error::UnknownVersion(str.to_string())),
+        }
+    }
+}
+
+#[cfg(feature = "transaction")]
+impl Version {
+    /// Returns the address version for a hash-mode & network.
+    ///
+    /// Only `P2PKH` uses the single-sig version, all other modes use the multi-sig version.
+    pub fn from_mode(mode: Mode, network: TransactionVersion) -> Self {
+        match (mode, network) {
+            (Mode::P2PKH, TransactionVersion::Mainnet) => Self::MainnetP2PKH,
+            (Mode::P2PKH, TransactionVersion::Testnet) => Self::TestnetP2PKH,
+            (_, TransactionVersion::Mainnet) => Self::MainnetP2SH,
+            (_, TransactionVersion::Testnet) => Self::TestnetP2SH,
+        }
+    }
+}
+
 /// A C32 address
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Address {
@@ -98,12 +247,14 @@ impl std::str::FromStr for Address {
     type Err = Error;
 
     fn from_str(str: &str) -> Result<Self, Self::Err> {
-        let (bytes, version) = c32_address_decode(str)?;
+        let (bytes, version) = c32_address_decode_strict(str)?;
         Ok(Self::new(Hash160::new(bytes), version))
     }
 }
 
 /// Encode a byte slice into a `C32` string.
+///
+/// Accepts input of any length, an empty slice encodes to an empty string.
 pub fn c32_encode<T>(slice: T) -> Result<String, Error>
 where
     T: AsRef<[u8]>,
@@ -229,6 +380,14 @@ where
     Ok(String::from_utf8(encoded)?)
 }
 
+/// Encode a byte slice into a `C32` string with a checksum & a typed address version.
+pub fn c32check_encode_typed<T>(hash: T, version: Version) -> Result<String, Error>
+where
+    T: AsRef<[u8]>,
+{
+    c32check_encode(hash, version as u8)
+}
+
 /// Decode a `C32` string with a checksum into a byte slice.
 pub fn c32check_decode<T>(str: T) -> Result<(Vec<u8>, u8), Error>
 where
@@ -262,16 +421,31 @@ where
 }
 
 /// Create a `C32` address from a byte slice and version.
+///
+/// The hash must be exactly 20 bytes (`Hash160`-sized).
 pub fn c32_address<T>(hash: T, version: u8) -> Result<String, Error>
 where
     T: AsRef<[u8]>,
 {
-    let hash = hash.as_ref();
-    if ![22, 26, 20, 21].contains(&version) {
-        return Err(Error::UnknownAddressVersion(version));
+    c32_address_typed(hash, Version::try_from(version)?)
+}
+
+/// Create a `C32` address from a byte slice and a typed address version.
+///
+/// The hash must be exactly 20 bytes (`Hash160`-sized).
+pub fn c32_address_typed<T>(hash: T, version: Version) -> Result<String, Error>
+where
+    T: AsRef<[u8]>,
+{
+    let len = hash.as_ref().len();
+
+    if len != HASH160_ENCODED_SIZE {
+        return Err(Error::BadAddress(format!(
+            "expected a {HASH160_ENCODED_SIZE}-byte hash, received {len} bytes"
+        )));
     }
 
-    let address = format!("S{}", c32check_encode(hash, version)?);
+    let address = format!("S{}", c32check_encode_typed(hash, version)?);
 
     Ok(address)
 }
@@ -294,6 +468,51 @@ where
     c32check_decode(&str[1..])
 }
 
+/// Decodes a `C32` address into a byte slice and version.
+///
+/// Unlike `c32_address_decode`, the decoded payload must be exactly 20 bytes (a `Hash160`).
+pub fn c32_address_decode_strict<T>(str: T) -> Result<(Vec<u8>, u8), Error>
+where
+    T: Into<String>,
+{
+    let str: String = str.into();
+    let (bytes, version) = c32_address_decode(str.as_str())?;
+
+    if bytes.len() != HASH160_ENCODED_SIZE {
+        return Err(Error::BadAddress(str));
+    }
+
+    Ok((bytes, version))
+}
+
+/// Returns whether `addr` is a burn address, a valid address with an all-zero hash.
+pub fn is_burn_address(addr: &str) -> bool {
+    c32_address_decode_strict(addr).is_ok_and(|(bytes, _)| bytes.iter().all(|b| *b == 0))
+}
+
+/// Classifies a `C32` address into its hash, hash-mode & network.
+///
+/// Returns the 20-byte hash, the inferred `Mode` & whether the address is a mainnet address.
+pub fn classify_address(addr: &str) -> Result<(Hash160, Mode, bool), Error> {
+    let (bytes, version) = c32_address_decode_strict(addr)?;
+    let mode = Mode::from_version(version)?;
+    let mainnet = Version::try_from(version)?.is_mainnet();
+    Ok((Hash160::new(bytes), mode, mainnet))
+}
+
+/// Recovers the P2PKH principal that signed a 32-byte message hash.
+#[cfg(feature = "transaction")]
+pub fn recover_principal(
+    message_hash: &[u8; 32],
+    sig: &MessageSignature,
+    network: TransactionVersion,
+) -> Result<String, Error> {
+    let pk = sig.recover(message_hash)?;
+    let hash = hash_p2pkh(&pk.serialize());
+    let version = Version::from_mode(Mode::P2PKH, network);
+    c32_address_typed(hash.as_bytes(), version)
+}
+
 /// Hashes a public key to a P2PKH address.
 pub fn hash_p2pkh(input: &[u8]) -> Hash160 {
     Hash160::from_slice(input)
@@ -402,6 +621,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_crypto_c32_encode_empty() {
+        assert_eq!(c32_encode([]).unwrap(), "");
+        assert!(c32_decode("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_crypto_c32_address_hash_length() {
+        let hash = hex_to_bytes("8a4d3f2e55c87f964bae8b2963b3a824a2e9c9ab").unwrap();
+        assert!(c32_address(&hash, 22).is_ok());
+
+        for len in [0, 19, 21, 32] {
+            let bytes = vec![0xab; len];
+            assert!(matches!(c32_address(&bytes, 22), Err(Error::BadAddress(_))));
+            assert!(matches!(
+                c32_address_typed(&bytes, Version::TestnetP2SH),
+                Err(Error::BadAddress(_))
+            ));
+        }
+    }
+
     #[test]
     fn test_crypto_c32_check_randomized_roundtrip() {
         let mut rng = thread_rng();
@@ -420,6 +660,261 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(feature = "transaction")]
+    fn test_crypto_c32_version_from_mode() {
+        let cases = [
+            (
+                Mode::P2PKH,
+                TransactionVersion::Mainnet,
+                Version::MainnetP2PKH,
+            ),
+            (
+                Mode::P2SH,
+                TransactionVersion::Mainnet,
+                Version::MainnetP2SH,
+            ),
+            (
+                Mode::P2WPKH,
+                TransactionVersion::Mainnet,
+                Version::MainnetP2SH,
+            ),
+            (
+                Mode::P2WSH,
+                TransactionVersion::Mainnet,
+                Version::MainnetP2SH,
+            ),
+            (
+                Mode::P2PKH,
+                TransactionVersion::Testnet,
+                Version::TestnetP2PKH,
+            ),
+            (
+                Mode::P2SH,
+                TransactionVersion::Testnet,
+                Version::TestnetP2SH,
+            ),
+            (
+                Mode::P2WPKH,
+                TransactionVersion::Testnet,
+                Version::TestnetP2SH,
+            ),
+            (
+                Mode::P2WSH,
+                TransactionVersion::Testnet,
+                Version::TestnetP2SH,
+            ),
+        ];
+
+        for (mode, network, expected) in cases {
+            let version = Version::from_mode(mode, network);
+            assert_eq!(version, expected);
+
+            let inverse = Mode::from_version(version as u8).unwrap();
+            let expected_mode = if mode == Mode::P2PKH {
+                Mode::P2PKH
+            } else {
+                Mode::P2SH
+            };
+            assert_eq!(inverse, expected_mode);
+        }
+    }
+
+    #[test]
+    fn test_crypto_c32_mode_from_unknown_version() {
+        for version in [0, 1, 19, 23, 25, 27, 255] {
+            assert_eq!(
+                Mode::from_version(version),
+                Err(Error::UnknownAddressVersion(version))
+            );
+        }
+    }
+
+    #[test]
+    fn test_crypto_c32_address_decode_strict() {
+        let hash = hex_to_bytes("8a4d3f2e55c87f964bae8b2963b3a824a2e9c9ab").unwrap();
+        let address = c32_address(&hash, 22).unwrap();
+
+        let (bytes, version) = c32_address_decode_strict(address.as_str()).unwrap();
+        assert_eq!(bytes, hash);
+        assert_eq!(version, 22);
+
+        let mut long = hash.clone();
+        long.push(0xff);
+        let long_address = format!("S{}", c32check_encode(&long, 22).unwrap());
+
+        assert!(c32_address_decode(long_address.as_str()).is_ok());
+        assert_eq!(
+            c32_address_decode_strict(long_address.as_str()),
+            Err(Error::BadAddress(long_address.clone()))
+        );
+        assert!(long_address.parse::<Address>().is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "transaction")]
+    fn test_crypto_c32_recover_principal() {
+        use secp256k1::Message;
+        use secp256k1::SecretKey;
+
+        use crate::crypto::secp256k1_context;
+        use crate::crypto::Sha256Hash;
+
+        let secp = secp256k1_context();
+        let sk_hex = "edf9aee84d9b7abc145504dde6726c64f369d37ee34ded868fabd876c26570bc";
+        let sk = SecretKey::from_slice(&hex_to_bytes(sk_hex).unwrap()).unwrap();
+        let pk = sk.public_key(secp);
+
+        let hash = Sha256Hash::from_slice(b"hello world").into_bytes();
+        let recoverable = secp.sign_ecdsa_recoverable(&Message::from_digest(hash), &sk);
+        let sig = MessageSignature::from_recov(recoverable).unwrap();
+
+        let mainnet = recover_principal(&hash, &sig, TransactionVersion::Mainnet).unwrap();
+        let testnet = recover_principal(&hash, &sig, TransactionVersion::Testnet).unwrap();
+
+        let pk_hash = hash_p2pkh(&pk.serialize());
+        assert_eq!(mainnet, c32_address(pk_hash.as_bytes(), 22).unwrap());
+        assert_eq!(testnet, c32_address(pk_hash.as_bytes(), 26).unwrap());
+        assert_eq!(mainnet, "SPAW66WC3G8WA5F28JVNG1NTRJ6H76E7EN5H6QQD");
+
+        let other = Sha256Hash::from_slice(b"goodbye world").into_bytes();
+        assert_ne!(
+            recover_principal(&other, &sig, TransactionVersion::Mainnet).unwrap(),
+            mainnet
+        );
+    }
+
+    #[test]
+    fn test_crypto_c32_address_typed() {
+        let hash = hex_to_bytes("a46ff88886c2ef9762d970b4d2c63678835bd39d").unwrap();
+        let versions = [
+            Version::MainnetP2PKH,
+            Version::MainnetP2SH,
+            Version::TestnetP2PKH,
+            Version::TestnetP2SH,
+        ];
+
+        for version in versions {
+            let typed = c32_address_typed(&hash, version).unwrap();
+            assert_eq!(typed, c32_address(&hash, version as u8).unwrap());
+            assert_eq!(
+                c32check_encode_typed(&hash, version).unwrap(),
+                c32check_encode(&hash, version as u8).unwrap()
+            );
+
+            let (decoded, byte) = c32_address_decode(typed).unwrap();
+            assert_eq!(decoded, hash);
+            assert_eq!(Version::try_from(byte).unwrap(), version);
+        }
+
+        assert_eq!(
+            c32_address_typed(&hash, Version::MainnetP2PKH).unwrap(),
+            "SP2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKNRV9EJ7"
+        );
+        assert_eq!(
+            Version::try_from(0x00),
+            Err(Error::UnknownAddressVersion(0x00))
+        );
+    }
+
+    #[test]
+    fn test_crypto_c32_classify_address() {
+        let hash = hex_to_bytes("a46ff88886c2ef9762d970b4d2c63678835bd39d").unwrap();
+        let cases = [
+            (Version::MainnetP2PKH, Mode::P2PKH, true),
+            (Version::MainnetP2SH, Mode::P2SH, true),
+            (Version::TestnetP2PKH, Mode::P2PKH, false),
+            (Version::TestnetP2SH, Mode::P2SH, false),
+        ];
+
+        for (version, mode, mainnet) in cases {
+            let addr = c32_address_typed(&hash, version).unwrap();
+            let (decoded, decoded_mode, decoded_mainnet) = classify_address(&addr).unwrap();
+            assert_eq!(decoded.as_bytes().as_slice(), hash.as_slice());
+            assert_eq!(decoded_mode, mode);
+            assert_eq!(decoded_mainnet, mainnet);
+        }
+
+        let unknown = c32check_encode(&hash, 0x01).unwrap();
+        assert_eq!(
+            classify_address(&format!("S{unknown}")),
+            Err(Error::UnknownAddressVersion(0x01))
+        );
+        assert!(matches!(classify_address("S"), Err(Error::BadAddress(_))));
+    }
+
+    #[test]
+    fn test_crypto_c32_mode_string() {
+        let cases = [
+            (Mode::P2PKH, "p2pkh"),
+            (Mode::P2SH, "p2sh"),
+            (Mode::P2WPKH, "p2wpkh"),
+            (Mode::P2WSH, "p2wsh"),
+        ];
+
+        for (mode, str) in cases {
+            assert_eq!(mode.to_string(), str);
+            assert_eq!(str.parse::<Mode>().unwrap(), mode);
+            assert_eq!(str.to_uppercase().parse::<Mode>().unwrap(), mode);
+        }
+
+        assert_eq!(
+            "p2tr".parse::<Mode>(),
+            Err(Error::UnknownMode("p2tr".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_crypto_c32_single_sig_mode() {
+        assert_eq!(Mode::from(SingleSigMode::P2PKH), Mode::P2PKH);
+        assert_eq!(Mode::from(SingleSigMode::P2WPKH), Mode::P2WPKH);
+    }
+
+    #[test]
+    fn test_crypto_c32_mode_try_from_api() {
+        let cases = [
+            ("P2PKH", Mode::P2PKH),
+            ("p2pkh", Mode::P2PKH),
+            ("P2SH", Mode::P2SH),
+            ("p2sh", Mode::P2SH),
+            ("P2WPKH", Mode::P2WPKH),
+            ("p2wpkh-p2sh", Mode::P2WPKH),
+            ("P2WPKH-P2SH", Mode::P2WPKH),
+            ("P2WSH", Mode::P2WSH),
+            ("p2wsh-p2sh", Mode::P2WSH),
+        ];
+
+        for (str, mode) in cases {
+            assert_eq!(Mode::try_from(str), Ok(mode));
+        }
+
+        assert_eq!(
+            Mode::try_from("p2sh-non-sequential"),
+            Err(Error::UnknownMode("p2sh-non-sequential".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_crypto_c32_version_string() {
+        let cases = [
+            (Version::MainnetP2PKH, "mainnet-p2pkh", true),
+            (Version::MainnetP2SH, "mainnet-p2sh", true),
+            (Version::TestnetP2PKH, "testnet-p2pkh", false),
+            (Version::TestnetP2SH, "testnet-p2sh", false),
+        ];
+
+        for (version, str, mainnet) in cases {
+            assert_eq!(version.to_string(), str);
+            assert_eq!(str.parse::<Version>().unwrap(), version);
+            assert_eq!(version.is_mainnet(), mainnet);
+        }
+
+        assert_eq!(
+            "mocknet-p2pkh".parse::<Version>(),
+            Err(Error::UnknownVersion("mocknet-p2pkh".to_string()))
+        );
+    }
+
     #[test]
     fn test_crypto_c32_p2pkh() {
         let input = b"bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu";
@@ -459,4 +954,63 @@ mod tests {
 
         assert_eq!(hash_p2wsh(2, &[pk, pk]).into_bytes(), expected_bytes);
     }
+
+    #[test]
+    fn test_crypto_c32_burn_address() {
+        let (bytes, version) = c32_address_decode(BURN_ADDRESS_MAINNET).unwrap();
+        assert_eq!(bytes, vec![0; 20]);
+        assert_eq!(version, 22);
+
+        let (bytes, version) = c32_address_decode(BURN_ADDRESS_TESTNET).unwrap();
+        assert_eq!(bytes, vec![0; 20]);
+        assert_eq!(version, 26);
+
+        assert!(is_burn_address(BURN_ADDRESS_MAINNET));
+        assert!(is_burn_address(BURN_ADDRESS_TESTNET));
+        assert!(!is_burn_address(
+            "SP3FGQ8Z7JY9BWYZ5WM53E0M9NK7WHJF0691NZ159"
+        ));
+        assert!(!is_burn_address("SP000000000000000000002Q6VF79"));
+        assert!(!is_burn_address("not-an-address"));
+    }
+
+    #[test]
+    fn test_crypto_c32_byte_map() {
+        for (index, ch) in C32_ALPHABET.iter().enumerate() {
+            let index = i8::try_from(index).unwrap();
+            assert_eq!(C32_BYTE_MAP[*ch as usize], index, "{}", *ch as char);
+            assert_eq!(
+                C32_BYTE_MAP[ch.to_ascii_lowercase() as usize],
+                index,
+                "{}",
+                ch.to_ascii_lowercase() as char
+            );
+        }
+
+        for (ch, value) in [
+            (b'O', 0),
+            (b'o', 0),
+            (b'I', 1),
+            (b'i', 1),
+            (b'L', 1),
+            (b'l', 1),
+        ] {
+            assert_eq!(C32_BYTE_MAP[ch as usize], value, "{}", ch as char);
+        }
+
+        let valid = C32_ALPHABET
+            .iter()
+            .flat_map(|ch| [*ch, ch.to_ascii_lowercase()])
+            .chain(*b"OoIiLl")
+            .collect::<Vec<_>>();
+
+        for ch in 0u8..128 {
+            if !valid.contains(&ch) {
+                assert_eq!(C32_BYTE_MAP[ch as usize], -1, "{}", ch as char);
+            }
+        }
+
+        assert_eq!(c32_decode("oIl").unwrap(), c32_decode("011").unwrap());
+        assert_eq!(c32_decode("U"), Err(Error::BadChar('U')));
+    }
 }
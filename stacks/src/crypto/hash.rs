@@ -6,6 +6,8 @@
 //
 // Usage of this file is permitted solely under a sanctioned license.
 
+use std::sync::OnceLock;
+
 use ring::digest::Context;
 use ring::digest::SHA256 as HashSha256;
 use ring::digest::SHA512_256 as HashSha512_256;
@@ -13,15 +15,28 @@ use ripemd::Digest;
 use ripemd::Ripemd160;
 use secp256k1::ecdsa::RecoverableSignature;
 use secp256k1::ecdsa::RecoveryId;
+use secp256k1::All;
 use secp256k1::Message;
 use secp256k1::PublicKey;
 use secp256k1::Secp256k1;
 use secp256k1::SecretKey;
+#[cfg(feature = "sha3")]
+use sha3::Keccak256;
+
+use crate::crypto::hex_to_bytes;
+#[cfg(feature = "transaction")]
+use crate::transaction::Transaction;
 
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 pub enum Error {
     #[error("Invalid message signature length, expected 65 bytes - got: {0}")]
     InvalidMessageSigLength(usize),
+    /// Received a hash of an unexpected length.
+    #[error("Invalid hash length, expected {0} bytes - got: {1}")]
+    InvalidHashLength(usize, usize),
+    /// `crypto::hex` crate errors.
+    #[error(transparent)]
+    Hex(#[from] crate::crypto::hex::Error),
     /// `secp256k1` crate errors.
     #[error(transparent)]
     Secp256k1(#[from] secp256k1::Error),
@@ -30,9 +45,19 @@ pub enum Error {
     TryFromInt(#[from] std::num::TryFromIntError),
 }
 
+/// Returns a shared `Secp256k1` context.
+///
+/// The context is created once & reused across signing, verification and
+/// deriving public keys from secret keys, creating a context per call is
+/// comparatively expensive. BIP32 child derivation does not go through it.
+pub fn secp256k1_context() -> &'static Secp256k1<All> {
+    static CONTEXT: OnceLock<Secp256k1<All>> = OnceLock::new();
+    CONTEXT.get_or_init(Secp256k1::new)
+}
+
 macro_rules! impl_hash_byte_array {
     ($name:ident, $ty:ty, $len:expr) => {
-        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
         pub struct $name(pub [$ty; $len]);
         impl $name {
             pub fn as_bytes(&self) -> &[$ty; $len] {
@@ -56,6 +81,11 @@ macro_rules! impl_hash_byte_array {
                 Ok(())
             }
         }
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                write!(f, "{}", self.hex())
+            }
+        }
         impl ::std::default::Default for $name {
             fn default() -> Self {
                 $name([0; $len])
@@ -125,6 +155,50 @@ impl Sha256Hash {
     }
 }
 
+/// An incremental `Sha256Hash` hasher, for input that is not held in memory at once.
+#[derive(Clone)]
+pub struct Sha256Hasher {
+    ctx: Context,
+}
+
+impl Sha256Hasher {
+    /// Creates a new `Sha256Hasher`.
+    pub fn new() -> Self {
+        Self {
+            ctx: Context::new(&HashSha256),
+        }
+    }
+
+    /// Feeds a chunk of input into the hasher.
+    pub fn update<T>(&mut self, bytes: T) -> &mut Self
+    where
+        T: AsRef<[u8]>,
+    {
+        self.ctx.update(bytes.as_ref());
+        self
+    }
+
+    /// Returns the `Sha256Hash` of all input fed so far.
+    pub fn finalize(&self) -> Sha256Hash {
+        let mut buff = [0u8; SHA256_ENCODED_SIZE];
+        let digest = self.ctx.clone().finish();
+        buff.copy_from_slice(digest.as_ref());
+        Sha256Hash(buff)
+    }
+}
+
+impl Default for Sha256Hasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for Sha256Hasher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Sha256Hasher").finish_non_exhaustive()
+    }
+}
+
 impl_hash_byte_array!(DSha256Hash, u8, SHA256_ENCODED_SIZE);
 impl DSha256Hash {
     /// Create a new `DSha256Hash` from a slice.
@@ -195,7 +269,7 @@ impl Hash160 {
         let sha = Sha256Hash::from_slice(bytes.as_ref());
         let bytes = sha.as_bytes();
         let ripemd = Ripemd160::digest(bytes);
-        buff.copy_from_slice(ripemd.as_slice());
+        buff.copy_from_slice(&ripemd);
         Self(buff)
     }
 
@@ -208,14 +282,62 @@ impl Hash160 {
     }
 }
 
+/// An incremental `Hash160` hasher, finalizing the `Sha256Hash` through `Ripemd160`.
+#[derive(Debug, Clone, Default)]
+pub struct Hash160Hasher {
+    sha: Sha256Hasher,
+}
+
+impl Hash160Hasher {
+    /// Creates a new `Hash160Hasher`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds a chunk of input into the hasher.
+    pub fn update<T>(&mut self, bytes: T) -> &mut Self
+    where
+        T: AsRef<[u8]>,
+    {
+        self.sha.update(bytes);
+        self
+    }
+
+    /// Returns the `Hash160` of all input fed so far.
+    pub fn finalize(&self) -> Hash160 {
+        let mut buff = [0u8; HASH160_ENCODED_SIZE];
+        let ripemd = Ripemd160::digest(self.sha.finalize().as_bytes());
+        buff.copy_from_slice(&ripemd);
+        Hash160(buff)
+    }
+}
+
+#[cfg(feature = "sha3")]
+impl_hash_byte_array!(Keccak256Hash, u8, SHA256_ENCODED_SIZE);
+#[cfg(feature = "sha3")]
+impl Keccak256Hash {
+    /// Create a new `Keccak256Hash` from a slice.
+    pub fn from_slice<T>(bytes: T) -> Self
+    where
+        T: AsRef<[u8]>,
+    {
+        let mut buff = [0u8; SHA256_ENCODED_SIZE];
+        let keccak = Keccak256::digest(bytes.as_ref());
+        buff.copy_from_slice(&keccak);
+        Self(buff)
+    }
+}
+
 impl_hash_byte_array!(MessageSignature, u8, MESSAGE_ENCODED_SIZE);
 impl MessageSignature {
     /// Creates a new `MessageSignature`.
+    ///
+    /// Panics if `bytes` is not 65 bytes long, use `from_slice` for untrusted input.
     pub fn new<T>(bytes: T) -> Self
     where
         T: AsRef<[u8]>,
     {
-        let mut buff = [0u8; 65];
+        let mut buff = [0u8; MESSAGE_ENCODED_SIZE];
         buff.copy_from_slice(bytes.as_ref());
         Self(buff)
     }
@@ -227,16 +349,26 @@ impl MessageSignature {
     {
         let len = bytes.as_ref().len();
 
-        if len != 65 {
+        if !Self::is_valid_length(len) {
             return Err(Error::InvalidMessageSigLength(len));
         }
 
-        let mut buff = [0u8; 65];
+        let mut buff = [0u8; MESSAGE_ENCODED_SIZE];
         buff.copy_from_slice(bytes.as_ref());
 
         Ok(Self(buff))
     }
 
+    /// Returns `true` if `len` is the length of an encoded signature, 65 bytes.
+    pub fn is_valid_length(len: usize) -> bool {
+        len == MESSAGE_ENCODED_SIZE
+    }
+
+    /// Returns `true` if this is the empty (all-zero) signature of an unsigned condition.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
     /// Creates a new `MessageSignature` from a recoverable signature.
     pub fn from_recov<T>(recov: T) -> Result<Self, Error>
     where
@@ -254,10 +386,53 @@ impl MessageSignature {
         let id = RecoveryId::from_i32(i32::from(bytes[0]))?;
         Ok(RecoverableSignature::from_compact(&bytes[1..], id)?)
     }
+
+    /// Returns the recovery id (`v`) of the signature.
+    pub fn recovery_id(&self) -> i32 {
+        i32::from(self.0[0])
+    }
+
+    /// Returns the `r` component of the signature.
+    pub fn r(&self) -> [u8; 32] {
+        let mut buff = [0u8; 32];
+        buff.copy_from_slice(&self.0[1..33]);
+        buff
+    }
+
+    /// Returns the `s` component of the signature.
+    pub fn s(&self) -> [u8; 32] {
+        let mut buff = [0u8; 32];
+        buff.copy_from_slice(&self.0[33..65]);
+        buff
+    }
+
+    /// Returns the DER encoding of the signature, dropping the recovery id.
+    pub fn to_der(&self) -> Result<Vec<u8>, Error> {
+        let sig = self.into_recov()?.to_standard();
+        Ok(sig.serialize_der().to_vec())
+    }
+
+    /// Recovers the public key that signed a 32-byte message hash.
+    pub fn recover(&self, hash: &[u8; 32]) -> Result<PublicKey, Error> {
+        let msg = Message::from_digest(*hash);
+        Ok(secp256k1_context().recover_ecdsa(&msg, &self.into_recov()?)?)
+    }
 }
 
 impl_hash_byte_array!(SignatureHash, u8, SHA256_ENCODED_SIZE);
 impl SignatureHash {
+    /// Returns the initial `SignatureHash` of a transaction.
+    ///
+    /// This is the hash every signer starts from, computed over the encoded
+    /// transaction with its origin reset (fee & nonce zeroed, signatures
+    /// cleared) and any sponsor replaced by an empty condition. The
+    /// presign-hash for each signer is then derived from it via
+    /// [`SignatureHash::make_presign_hash`].
+    #[cfg(feature = "transaction")]
+    pub fn from_transaction(tx: &Transaction) -> Result<Self, crate::transaction::Error> {
+        tx.initial_hash()
+    }
+
     /// Create a new `SignatureHash` from a slice.
     pub fn from_slice<T>(bytes: T) -> Self
     where
@@ -267,6 +442,32 @@ impl SignatureHash {
         Self(hasher.into_bytes())
     }
 
+    /// Returns the hex encoded `SignatureHash`, the inverse of
+    /// [`SignatureHash::from_hex`].
+    pub fn to_hex(&self) -> String {
+        self.hex()
+    }
+
+    /// Parses a hex encoded `SignatureHash`, with or without a `0x` prefix.
+    ///
+    /// Unlike [`SignatureHash::from_slice`], the input is not hashed.
+    pub fn from_hex(str: &str) -> Result<Self, Error> {
+        let str = str.strip_prefix("0x").unwrap_or(str);
+        let bytes = hex_to_bytes(str)?;
+        let len = bytes.len();
+
+        bytes
+            .try_into()
+            .map(Self)
+            .map_err(|_| Error::InvalidHashLength(SHA256_ENCODED_SIZE, len))
+    }
+
+    /// Signs the next presign-hash & returns the signature and the
+    /// postsign-hash.
+    ///
+    /// Signing uses deterministic nonces (RFC6979), no randomness is drawn
+    /// from the environment, signing the same hash with the same key always
+    /// produces identical bytes.
     pub fn next_signature<T>(
         hash: Self,
         typ: u8,
@@ -277,7 +478,7 @@ impl SignatureHash {
     where
         T: Into<SecretKey>,
     {
-        let secp = Secp256k1::new();
+        let secp = secp256k1_context();
         let pre_sign = Self::make_presign_hash(hash, typ, fee, nonce);
 
         let msg = Message::from_digest_slice(pre_sign.as_bytes())?;
@@ -300,7 +501,7 @@ impl SignatureHash {
         T: Into<MessageSignature>,
     {
         let signature = signature.into();
-        let secp = secp256k1::Secp256k1::new();
+        let secp = secp256k1_context();
         let pre_sign = Self::make_presign_hash(hash, typ, fee, nonce);
 
         let recoverable = signature.into_recov()?;
@@ -312,6 +513,9 @@ impl SignatureHash {
         Ok((pubk, next))
     }
 
+    /// Creates the presign-hash that is signed by the next signer.
+    ///
+    /// `sha512_256(hash || auth-type || fee (u64 BE) || nonce (u64 BE))`
     pub fn make_presign_hash(hash: Self, typ: u8, fee: u64, nonce: u64) -> Self {
         let mut buff = vec![];
 
@@ -323,6 +527,9 @@ impl SignatureHash {
         Self::from_slice(&buff)
     }
 
+    /// Creates the postsign-hash that is passed on to the next signer.
+    ///
+    /// `sha512_256(presign-hash || key-encoding || signature)`
     pub fn make_postsign_hash<T>(hash: Self, sig: T) -> Self
     where
         T: Into<MessageSignature>,
@@ -336,3 +543,129 @@ impl SignatureHash {
         Self::from_slice(&buff)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crypto_hash_secp256k1_context_cached() {
+        let sk = SecretKey::from_slice(&[0x01; 32]).unwrap();
+        assert!(std::ptr::eq(secp256k1_context(), secp256k1_context()));
+        assert_eq!(
+            sk.public_key(secp256k1_context()),
+            sk.public_key(&Secp256k1::new())
+        );
+    }
+
+    #[test]
+    fn test_crypto_hash_message_signature_components() {
+        let secp = secp256k1_context();
+        let sk = SecretKey::from_slice(&[0x01; 32]).unwrap();
+        let hash = Sha256Hash::from_slice(b"hello world").into_bytes();
+        let msg = Message::from_digest(hash);
+
+        let sig = MessageSignature::from_recov(secp.sign_ecdsa_recoverable(&msg, &sk)).unwrap();
+        let bytes = sig.as_bytes();
+
+        assert_eq!(sig.recovery_id(), i32::from(bytes[0]));
+        assert_eq!(sig.r().as_slice(), &bytes[1..33]);
+        assert_eq!(sig.s().as_slice(), &bytes[33..65]);
+
+        let der = sig.to_der().unwrap();
+        let standard = secp256k1::ecdsa::Signature::from_der(&der).unwrap();
+        assert_eq!(standard.serialize_compact().as_slice(), &bytes[1..]);
+        assert!(secp
+            .verify_ecdsa(&msg, &standard, &sk.public_key(secp))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_crypto_hash_message_signature_empty() {
+        assert!(MessageSignature::default().is_empty());
+
+        let secp = secp256k1_context();
+        let sk = SecretKey::from_slice(&[0x01; 32]).unwrap();
+        let msg = Message::from_digest(Sha256Hash::from_slice(b"hello world").into_bytes());
+        let sig = MessageSignature::from_recov(secp.sign_ecdsa_recoverable(&msg, &sk)).unwrap();
+        assert!(!sig.is_empty());
+
+        assert!(MessageSignature::is_valid_length(65));
+        assert!(!MessageSignature::is_valid_length(64));
+        assert!(matches!(
+            MessageSignature::from_slice([0u8; 64]),
+            Err(Error::InvalidMessageSigLength(64))
+        ));
+    }
+
+    #[test]
+    fn test_crypto_hash_message_signature_from_slice() {
+        let bytes = (0..66).collect::<Vec<u8>>();
+
+        for len in [64, 66] {
+            assert!(matches!(
+                MessageSignature::from_slice(&bytes[..len]),
+                Err(Error::InvalidMessageSigLength(l)) if l == len
+            ));
+        }
+
+        let sig = MessageSignature::from_slice(&bytes[..65]).unwrap();
+        assert_eq!(sig.as_bytes().as_slice(), &bytes[..65]);
+        assert_eq!(sig, MessageSignature::new(&bytes[..65]));
+    }
+
+    #[test]
+    #[cfg(feature = "sha3")]
+    fn test_crypto_hash_keccak256() {
+        let empty = Keccak256Hash::from_slice([]);
+        let expected = "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470";
+        assert_eq!(empty.hex(), expected);
+
+        let hello = Keccak256Hash::from_slice(b"hello world");
+        let expected = "47173285a8d7341e5e972fc677286384f802f8ef42a5ec5f03bbfa254cb01fad";
+        assert_eq!(hello.hex(), expected);
+    }
+
+    #[test]
+    fn test_crypto_hash_signature_hash_hex() {
+        let hash = SignatureHash::from_slice(b"transaction");
+        let hex = hash.to_hex();
+
+        assert_eq!(hex.len(), 64);
+        assert_eq!(hash.to_string(), hex);
+        assert_eq!(SignatureHash::from_hex(&hex).unwrap(), hash);
+        assert_eq!(SignatureHash::from_hex(&format!("0x{hex}")).unwrap(), hash);
+        assert_eq!(SignatureHash::from(*hash.as_bytes()), hash);
+
+        assert!(matches!(
+            SignatureHash::from_hex("0102"),
+            Err(Error::InvalidHashLength(32, 2))
+        ));
+        assert!(matches!(SignatureHash::from_hex("zz"), Err(Error::Hex(_))));
+    }
+
+    #[test]
+    fn test_crypto_hash_streaming() {
+        let first = b"(define-public (hello)";
+        let second = b" (ok u1))";
+        let whole = [&first[..], &second[..]].concat();
+
+        let sha = Sha256Hasher::new().update(first).update(second).finalize();
+        assert_eq!(sha, Sha256Hash::from_slice(&whole));
+
+        let mut hasher = Hash160Hasher::new();
+        for chunk in whole.chunks(3) {
+            hasher.update(chunk);
+        }
+        assert_eq!(hasher.finalize(), Hash160::from_slice(&whole));
+        assert_eq!(
+            Hash160Hasher::new().update(first).update(second).finalize(),
+            Hash160::from_slice(&whole)
+        );
+
+        assert_eq!(
+            Sha256Hasher::default().finalize(),
+            Sha256Hash::from_slice([])
+        );
+    }
+}
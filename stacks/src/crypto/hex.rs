@@ -6,7 +6,45 @@
 //
 // Usage of this file is permitted solely under a sanctioned license.
 
-use std::fmt::Write;
+/// The lowercase hex alphabet, used for encoding.
+pub(crate) const HEX_ALPHABET: &[u8; 16] = b"0123456789abcdef";
+
+/// Lookup table mapping every byte to its two hex characters.
+pub(crate) const HEX_ENCODE_TABLE: [[u8; 2]; 256] = {
+    let mut table = [[0u8; 2]; 256];
+    let mut i = 0;
+
+    while i < 256 {
+        table[i] = [HEX_ALPHABET[i >> 4], HEX_ALPHABET[i & 0x0f]];
+        i += 1;
+    }
+
+    table
+};
+
+/// Lookup table mapping every ASCII character to its nibble, `0xff` if invalid.
+pub(crate) const HEX_DECODE_TABLE: [u8; 256] = {
+    let mut table = [0xffu8; 256];
+    let mut i = 0u8;
+
+    while i < 10 {
+        table[(b'0' + i) as usize] = i;
+        i += 1;
+    }
+
+    let mut i = 0u8;
+
+    while i < 6 {
+        table[(b'a' + i) as usize] = 10 + i;
+        table[(b'A' + i) as usize] = 10 + i;
+        i += 1;
+    }
+
+    table
+};
+
+/// The number of bytes encoded per chunk by `bytes_to_hex_into`.
+const HEX_ENCODE_CHUNK: usize = 256;
 
 /// Error variants for Hex encoding/decoding.
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
@@ -40,40 +78,42 @@ impl<'a> Iterator for HexIterator<'a> {
     type Item = Result<u8, Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let hi = match self.iter.next()? {
-            b @ b'0'..=b'9' => b - b'0',
-            b @ b'a'..=b'f' => b - b'a' + 10,
-            b @ b'A'..=b'F' => b - b'A' + 10,
-            _ => return Some(Err(Error::BadChar)),
-        };
-
-        let lo = match self.iter.next()? {
-            b @ b'0'..=b'9' => b - b'0',
-            b @ b'a'..=b'f' => b - b'a' + 10,
-            b @ b'A'..=b'F' => b - b'A' + 10,
-            _ => return Some(Err(Error::BadChar)),
-        };
-
-        Some(Ok((hi << 4) | lo))
+        let hi = self.iter.next()?;
+        let lo = self.iter.next()?;
+        Some(decode_pair(hi, lo))
     }
 }
 
+/// Decodes a pair of hex characters into a byte.
+#[inline]
+fn decode_pair(hi: u8, lo: u8) -> Result<u8, Error> {
+    let hi = HEX_DECODE_TABLE[usize::from(hi)];
+    let lo = HEX_DECODE_TABLE[usize::from(lo)];
+
+    // both nibbles are checked at once, invalid characters map to `0xff`
+    if (hi | lo) & 0xf0 != 0 {
+        return Err(Error::BadChar);
+    }
+
+    Ok((hi << 4) | lo)
+}
+
 /// Convert a hex string to a byte array.
 pub fn hex_to_bytes<T>(str: T) -> Result<Vec<u8>, Error>
 where
     T: Into<String>,
 {
     let str = str.into();
-    let mut buff = Vec::with_capacity(str.len() / 2);
-    let iter = HexIterator::new(&str)?;
-
-    for opt in iter {
-        let byte = opt?;
 
-        buff.push(byte);
+    if !str.len().is_multiple_of(2) {
+        let len = str.len();
+        return Err(Error::UnpaddedHex(str, len));
     }
 
-    Ok(buff)
+    str.as_bytes()
+        .chunks_exact(2)
+        .map(|pair| decode_pair(pair[0], pair[1]))
+        .collect()
 }
 
 /// Convert a byte array to a hex string.
@@ -82,13 +122,77 @@ where
     T: AsRef<[u8]>,
 {
     let slice = slice.as_ref();
-    let mut buff = String::with_capacity(slice.len());
+    let buff = slice
+        .iter()
+        .flat_map(|byte| HEX_ENCODE_TABLE[usize::from(*byte)])
+        .collect::<Vec<u8>>();
+
+    String::from_utf8(buff).expect("the hex encode table is ascii")
+}
+
+/// Appends the hex encoding of a byte array to a caller-provided `String`.
+///
+/// Reusing `out` across calls avoids an allocation per call.
+pub fn bytes_to_hex_into(bytes: &[u8], out: &mut String) {
+    let mut buff = [0u8; HEX_ENCODE_CHUNK * 2];
+    out.reserve(bytes.len() * 2);
+
+    for chunk in bytes.chunks(HEX_ENCODE_CHUNK) {
+        for (pair, byte) in buff.chunks_exact_mut(2).zip(chunk) {
+            pair.copy_from_slice(&HEX_ENCODE_TABLE[usize::from(*byte)]);
+        }
+
+        let hex = std::str::from_utf8(&buff[..chunk.len() * 2]);
+        out.push_str(hex.expect("the hex encode table is ascii"));
+    }
+}
+
+/// Convert a fixed-size byte array to a stack-allocated hex string.
+pub fn bytes_to_hex_fixed<const N: usize>(bytes: &[u8; N]) -> FixedHex<N> {
+    let mut buff = [[0u8; 2]; N];
+
+    for (pair, byte) in buff.iter_mut().zip(bytes) {
+        *pair = HEX_ENCODE_TABLE[usize::from(*byte)];
+    }
+
+    FixedHex(buff)
+}
+
+/// A stack-allocated hex string, encoding `N` bytes.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FixedHex<const N: usize>([[u8; 2]; N]);
+
+impl<const N: usize> FixedHex<N> {
+    /// Returns the hex string as a `&str`.
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(self.0.as_flattened()).expect("the hex encode table is ascii")
+    }
+}
 
-    for byte in slice {
-        write!(buff, "{byte:02x}").unwrap();
+impl<const N: usize> std::ops::Deref for FixedHex<N> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
     }
+}
+
+impl<const N: usize> AsRef<str> for FixedHex<N> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
 
-    buff
+impl<const N: usize> std::fmt::Display for FixedHex<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<const N: usize> std::fmt::Debug for FixedHex<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "FixedHex({})", self.as_str())
+    }
 }
 
 #[cfg(test)]
@@ -122,6 +226,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_crypto_hex_into_and_fixed() {
+        use rand::thread_rng;
+        use rand::RngCore;
+
+        let mut rng = thread_rng();
+        let mut out = String::new();
+
+        for _ in 0..1_000 {
+            let mut input = [0u8; 20];
+            rng.fill_bytes(&mut input);
+
+            out.clear();
+            bytes_to_hex_into(&input, &mut out);
+            assert_eq!(out, bytes_to_hex(input));
+            assert_eq!(bytes_to_hex_fixed(&input).as_str(), bytes_to_hex(input));
+        }
+
+        let fixed = bytes_to_hex_fixed(&[0x00, 0xab, 0xff]);
+        assert_eq!(&*fixed, "00abff");
+        assert_eq!(fixed.to_string(), "00abff");
+        assert_eq!(bytes_to_hex_fixed(&[]).as_str(), "");
+
+        let mut out = String::from("0x");
+        bytes_to_hex_into(&[0x01, 0x02], &mut out);
+        assert_eq!(out, "0x0102");
+    }
+
     #[test]
     fn test_crypto_hex_error() {
         let bad_len = "0123456789abcdef0";
@@ -134,4 +266,41 @@ mod tests {
             assert_eq!(hex_to_bytes(char), Err(Error::BadChar));
         }
     }
+
+    #[test]
+    fn test_crypto_hex_matches_reference() {
+        use std::fmt::Write;
+
+        use rand::thread_rng;
+        use rand::RngCore;
+
+        let mut rng = thread_rng();
+        let mut input = vec![0u8; 64 * 1024 + 3];
+        rng.fill_bytes(&mut input);
+
+        let reference = input.iter().fold(String::new(), |mut acc, b| {
+            write!(acc, "{b:02x}").unwrap();
+            acc
+        });
+        assert_eq!(bytes_to_hex(&input), reference);
+
+        let mut out = String::new();
+        bytes_to_hex_into(&input, &mut out);
+        assert_eq!(out, reference);
+
+        assert_eq!(hex_to_bytes(reference.to_uppercase()).unwrap(), input);
+        assert_eq!(hex_to_bytes(reference).unwrap(), input);
+
+        for byte in 0..=u8::MAX {
+            let pair = [byte, b'0'];
+            let expected = char::from(byte)
+                .to_digit(16)
+                .map(|d| u8::try_from(d << 4).unwrap());
+            let received = std::str::from_utf8(&pair)
+                .ok()
+                .and_then(|str| hex_to_bytes(str).ok())
+                .map(|bytes| bytes[0]);
+            assert_eq!(received, expected);
+        }
+    }
 }
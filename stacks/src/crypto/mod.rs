@@ -10,22 +10,43 @@ pub use crate::crypto::b58::b58_decode;
 pub use crate::crypto::b58::b58_encode;
 pub use crate::crypto::b58::base58check_decode;
 pub use crate::crypto::b58::base58check_encode;
+pub use crate::crypto::bech32::bech32_decode;
+pub use crate::crypto::bech32::bech32_encode;
+pub use crate::crypto::bech32::BitcoinAddress;
 pub use crate::crypto::c32::c32_address;
 pub use crate::crypto::c32::c32_address_decode;
+pub use crate::crypto::c32::c32_address_decode_strict;
+pub use crate::crypto::c32::c32_address_typed;
 pub use crate::crypto::c32::c32_decode;
 pub use crate::crypto::c32::c32_encode;
 pub use crate::crypto::c32::c32check_decode;
 pub use crate::crypto::c32::c32check_encode;
+pub use crate::crypto::c32::c32check_encode_typed;
+pub use crate::crypto::c32::classify_address;
+pub use crate::crypto::c32::is_burn_address;
+#[cfg(feature = "transaction")]
+pub use crate::crypto::c32::recover_principal;
+pub use crate::crypto::c32::BURN_ADDRESS_MAINNET;
+pub use crate::crypto::c32::BURN_ADDRESS_TESTNET;
+pub use crate::crypto::hash::secp256k1_context;
 pub use crate::crypto::hash::DSha256Hash;
 pub use crate::crypto::hash::Hash160;
+pub use crate::crypto::hash::Hash160Hasher;
+#[cfg(feature = "sha3")]
+pub use crate::crypto::hash::Keccak256Hash;
 pub use crate::crypto::hash::MessageSignature;
 pub use crate::crypto::hash::Sha256Hash;
+pub use crate::crypto::hash::Sha256Hasher;
 pub use crate::crypto::hash::Sha512_256Hash;
 pub use crate::crypto::hash::SignatureHash;
 pub use crate::crypto::hex::bytes_to_hex;
+pub use crate::crypto::hex::bytes_to_hex_fixed;
+pub use crate::crypto::hex::bytes_to_hex_into;
 pub use crate::crypto::hex::hex_to_bytes;
+pub use crate::crypto::hex::FixedHex;
 
 pub mod b58;
+pub mod bech32;
 pub mod c32;
 pub mod hash;
 pub mod hex;
@@ -17,7 +17,23 @@
     clippy::too_many_arguments,
     clippy::large_enum_variant,
     clippy::result_large_err,
-    clippy::similar_names
+    clippy::similar_names,
+    clippy::implicit_clone,
+    clippy::manual_is_multiple_of,
+    clippy::elidable_lifetime_names,
+    clippy::unnecessary_trailing_comma
+)]
+#![cfg_attr(
+    test,
+    allow(
+        clippy::unreadable_literal,
+        clippy::needless_borrows_for_generic_args,
+        clippy::uninlined_format_args,
+        clippy::explicit_into_iter_loop,
+        clippy::vec_init_then_push,
+        clippy::semicolon_if_nothing_returned,
+        clippy::useless_vec
+    )
 )]
 
 #[cfg(feature = "clarity")]
@@ -26,6 +42,9 @@ pub mod clarity;
 #[cfg(feature = "crypto")]
 pub mod crypto;
 
+#[cfg(feature = "clarity")]
+pub mod pox;
+
 #[cfg(feature = "rpc")]
 pub mod rpc;
 
@@ -48,12 +67,19 @@ pub mod derive {
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
+    #[cfg(feature = "crypto")]
     /// `crypto::b58` crate errors.
     #[error(transparent)]
     Base58(#[from] crypto::b58::Error),
+    #[cfg(feature = "crypto")]
+    /// `crypto::bech32` crate errors.
+    #[error(transparent)]
+    Bech32(#[from] crypto::bech32::Error),
+    #[cfg(feature = "crypto")]
     /// `crypto::c32` crate errors.
     #[error(transparent)]
     C32(#[from] crypto::c32::Error),
+    #[cfg(feature = "crypto")]
     /// `crypto::hex` crate errors.
     #[error(transparent)]
     Hex(#[from] crypto::hex::Error),
@@ -61,6 +87,10 @@ pub enum Error {
     /// `clarity` crate errors.
     #[error(transparent)]
     Clarity(#[from] clarity::Error),
+    #[cfg(feature = "clarity")]
+    /// `pox` crate errors.
+    #[error(transparent)]
+    Pox(#[from] pox::Error),
     #[cfg(feature = "transaction")]
     /// `transaction` crate errors.
     #[error(transparent)]
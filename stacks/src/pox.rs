@@ -0,0 +1,97 @@
+// © 2024 Max Karou. All Rights Reserved.
+// Licensed under Apache Version 2.0, or MIT License, at your discretion.
+//
+// Apache License: http://www.apache.org/licenses/LICENSE-2.0
+// MIT License: http://opensource.org/licenses/MIT
+//
+// Usage of this file is permitted solely under a sanctioned license.
+
+use crate::clarity;
+use crate::clarity::Tuple;
+use crate::crypto::bech32;
+use crate::crypto::BitcoinAddress;
+
+/// Error variants for `PoX` helpers.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum Error {
+    /// `crypto::bech32` crate errors.
+    #[error(transparent)]
+    Bech32(#[from] bech32::Error),
+}
+
+/// Converts a bitcoin address into the `PoX` address tuple expected by `pox-4`.
+///
+/// The tuple has the shape `(tuple (version (buff 1)) (hashbytes (buff 32)))`, where
+/// `hashbytes` is 20 bytes for P2PKH, P2SH & P2WPKH, and 32 bytes for P2WSH & P2TR.
+pub fn to_pox_address_tuple(btc_address: &str) -> Result<Tuple, Error> {
+    let address = btc_address.parse::<BitcoinAddress>()?;
+    let (version, hashbytes) = address.pox_address()?;
+
+    Ok(clarity!(
+        Tuple,
+        ("version", clarity!(Buffer, [version])),
+        ("hashbytes", clarity!(Buffer, hashbytes))
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clarity::Buffer;
+    use crate::crypto::base58check_encode;
+    use crate::crypto::bytes_to_hex;
+
+    #[test]
+    fn test_pox_address_tuple() {
+        let hash = [0xab; 20];
+        let cases = [
+            (base58check_encode(hash, 0), 0x00, 20),
+            (base58check_encode(hash, 5), 0x01, 20),
+            (base58check_encode(hash, 111), 0x00, 20),
+            (base58check_encode(hash, 196), 0x01, 20),
+            (
+                "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4".to_string(),
+                0x04,
+                20,
+            ),
+            (
+                "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7".to_string(),
+                0x05,
+                32,
+            ),
+            (
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0".to_string(),
+                0x06,
+                32,
+            ),
+        ];
+
+        for (address, exp_version, exp_len) in cases {
+            let tuple = to_pox_address_tuple(&address).unwrap();
+            let version = tuple.get_as::<Buffer, _>("version").unwrap();
+            let hashbytes = tuple.get_as::<Buffer, _>("hashbytes").unwrap();
+
+            assert_eq!(version.value(), &vec![exp_version]);
+            assert_eq!(hashbytes.value().len(), exp_len);
+        }
+
+        let tuple = to_pox_address_tuple("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4").unwrap();
+        let hashbytes = tuple.get_as::<Buffer, _>("hashbytes").unwrap();
+        assert_eq!(
+            bytes_to_hex(hashbytes.value()),
+            "751e76e8199196d454941c45d1b3a323f1433bd6"
+        );
+    }
+
+    #[test]
+    fn test_pox_address_tuple_invalid() {
+        assert!(matches!(
+            to_pox_address_tuple("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3tb"),
+            Err(Error::Bech32(bech32::Error::BadChar('b')))
+        ));
+        assert!(matches!(
+            to_pox_address_tuple("not-an-address"),
+            Err(Error::Bech32(bech32::Error::Base58(_)))
+        ));
+    }
+}
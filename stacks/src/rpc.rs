@@ -6,14 +6,26 @@
 //
 // Usage of this file is permitted solely under a sanctioned license.
 
+use std::collections::HashMap;
 use std::format as f;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
 
 use serde::Deserialize;
 use serde::Serialize;
 
 use crate::clarity;
+use crate::clarity::decode_clarity_type;
+use crate::clarity::Clarity;
 use crate::clarity::Codec;
 use crate::clarity::FnArguments;
+use crate::crypto;
+use crate::crypto::hex_to_bytes;
+use crate::crypto::Sha256Hash;
+use crate::crypto::BURN_ADDRESS_MAINNET;
+use crate::transaction::SmartContractPayload;
 use crate::transaction::Transaction;
 
 /// Error type for the `rpc` module.
@@ -28,8 +40,40 @@ pub enum Error {
     /// `clarity` crate errors.
     #[error(transparent)]
     Clarity(#[from] clarity::Error),
+    /// `crypto::hex` crate errors.
+    #[error(transparent)]
+    Hex(#[from] crypto::hex::Error),
+    /// The node could not estimate a fee.
+    #[error("Fee estimation failed: {0}")]
+    FeeEstimation(String),
+    /// A transaction was still pending when the timeout elapsed.
+    #[error("Transaction {0} was not confirmed before the timeout")]
+    ConfirmationTimeout(String),
+    /// The node would reject the transaction.
+    #[error("Transaction would be rejected: {0}")]
+    Rejected(BroadcastErr),
+    /// The response contained a malformed field.
+    #[error("Bad response field `{0}` - received: {1}")]
+    BadResponse(String, String),
+    /// A read-only call failed.
+    #[error("Read-only call failed: {0}")]
+    ReadOnly(String),
 }
 
+/// The default timeout of a request, covering connect, send & receive.
+pub(crate) const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The default number of idle connections kept alive per host.
+pub(crate) const DEFAULT_MAX_IDLE_PER_HOST: usize = 4;
+
+/// The encoded length of a single-sig standard transaction, excluding the
+/// payload & post-conditions.
+pub(crate) const STANDARD_TX_OVERHEAD: usize = 115;
+
+/// The sender of read-only calls made without an explicit sender, the mainnet
+/// burn address.
+pub const DEFAULT_READ_ONLY_SENDER: &str = BURN_ADDRESS_MAINNET;
+
 /// The response from the `get_info` rpc method.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct NodeInfoResponse {
@@ -75,6 +119,47 @@ pub struct AddressInfoResponse {
     pub nonce_proof: String,
 }
 
+/// The response from the `/extended/v1/address/{address}/nonces` endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+struct NonceResponse {
+    /// The next nonce, accounting for pending mempool transactions.
+    possible_next_nonce: u64,
+}
+
+/// The response from the `/extended/v1/address/{address}/stx` endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+struct StxBalanceResponse {
+    /// The locked balance in micro-stacks, as a decimal string.
+    locked: String,
+    /// The id of the transaction that locked the balance.
+    #[serde(default)]
+    lock_tx_id: String,
+    /// The stacks block height the balance was locked at.
+    #[serde(default)]
+    lock_height: u64,
+    /// The burnchain block height the balance was locked at.
+    #[serde(default)]
+    burnchain_lock_height: u64,
+    /// The burnchain block height the balance unlocks at.
+    #[serde(default)]
+    burnchain_unlock_height: u64,
+}
+
+/// A locked STX balance & the height it becomes spendable at.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct UnlockEvent {
+    /// The locked amount in micro-stacks.
+    pub amount: u128,
+    /// The id of the transaction that locked the balance.
+    pub lock_tx_id: String,
+    /// The stacks block height the balance was locked at.
+    pub lock_height: u64,
+    /// The burnchain block height the balance was locked at.
+    pub burnchain_lock_height: u64,
+    /// The burnchain block height the balance unlocks at.
+    pub burnchain_unlock_height: u64,
+}
+
 /// The response from the `estimate_fee` method.
 #[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -96,6 +181,13 @@ pub struct FeeOk {
     pub estimations: Vec<FeeEstimate>,
 }
 
+impl FeeOk {
+    /// Returns the middle fee estimate (low, middle, high).
+    pub fn middle(&self) -> Option<&FeeEstimate> {
+        self.estimations.get(self.estimations.len() / 2)
+    }
+}
+
 /// A single fee estimate object.
 #[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub struct FeeEstimate {
@@ -177,10 +269,311 @@ pub struct ReadOnlyErr {
     pub cause: String,
 }
 
+/// The runtime cost of a read-only call, as reported by the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct RuntimeCost {
+    /// The number of reads.
+    pub read_count: u64,
+    /// The number of bytes read.
+    pub read_length: u64,
+    /// The number of writes.
+    pub write_count: u64,
+    /// The number of bytes written.
+    pub write_length: u64,
+    /// The runtime cost.
+    pub runtime: u64,
+}
+
+/// The result of the `call_read_only_with_cost` method.
+#[derive(Debug, Clone)]
+pub struct ReadOnlyWithCost {
+    /// The decoded result of the call.
+    pub value: Box<dyn Clarity>,
+    /// The runtime cost of the call, `None` if the node doesn't report it.
+    pub cost: Option<RuntimeCost>,
+    /// The raw JSON response of the node.
+    pub raw: serde_json::Value,
+}
+
+/// The response from the `call_read_only_with_cost` method.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+struct ReadOnlyCostResponse {
+    /// Whether the call was successful.
+    okay: bool,
+    /// The hex encoded result.
+    #[serde(default)]
+    result: Option<String>,
+    /// The error cause.
+    #[serde(default)]
+    cause: Option<String>,
+    /// The runtime cost of the call.
+    #[serde(default)]
+    cost: Option<RuntimeCost>,
+}
+
+/// The response from the `get_contract_source` method.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ContractSource {
+    /// The clarity source of the contract.
+    pub source: String,
+    /// The block height the contract was published at.
+    pub publish_height: u64,
+    /// The MARF proof, only present when requested.
+    #[serde(default)]
+    pub proof: Option<String>,
+}
+
+/// The response from the `/v2/map_entry` endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+struct MapEntryResponse {
+    /// The hex-encoded `(optional ...)` value.
+    data: String,
+}
+
+/// The type of a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TxType {
+    /// A token transfer transaction.
+    TokenTransfer,
+    /// A contract call transaction.
+    ContractCall,
+    /// A contract deploy transaction.
+    SmartContract,
+    /// A coinbase transaction.
+    Coinbase,
+    /// A poison microblock transaction.
+    PoisonMicroblock,
+    /// A tenure change transaction.
+    TenureChange,
+    /// Any other transaction type.
+    #[serde(other)]
+    Unknown,
+}
+
+/// The status of a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionStatus {
+    /// The transaction is in the mempool.
+    Pending,
+    /// The transaction was confirmed & succeeded.
+    Success,
+    /// The transaction was confirmed, but returned an `(err ...)` response.
+    AbortByResponse,
+    /// The transaction was confirmed, but failed a post-condition.
+    AbortByPostCondition,
+    /// The transaction was replaced by a higher fee transaction.
+    DroppedReplaceByFee,
+    /// The transaction was replaced by a transaction on another fork.
+    DroppedReplaceAcrossFork,
+    /// The transaction was dropped for being too expensive.
+    DroppedTooExpensive,
+    /// The transaction was dropped for being stale.
+    DroppedStaleGarbageCollect,
+    /// The transaction was dropped for being problematic.
+    DroppedProblematic,
+    /// Any other transaction status.
+    #[serde(other)]
+    Unknown,
+}
+
+/// The response from the `fetch_transaction` method.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct TransactionInfo {
+    /// The transaction id.
+    pub tx_id: String,
+    /// The transaction type.
+    pub tx_type: TxType,
+    /// The transaction status.
+    pub tx_status: TransactionStatus,
+    /// The height of the block the transaction was included in, if any.
+    #[serde(default)]
+    pub block_height: Option<u64>,
+}
+
+/// A single confirmed transaction object.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct TxSummary {
+    /// The transaction id.
+    pub tx_id: String,
+    /// The transaction type.
+    pub tx_type: TxType,
+    /// The transaction status.
+    pub tx_status: String,
+    /// The height of the block the transaction was included in.
+    pub block_height: u64,
+    /// The unix timestamp of the anchoring burn block.
+    pub burn_block_time: u64,
+}
+
+/// The response from the `/extended/v1/address/{address}/transactions` endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TxSummaryPage {
+    /// The transactions in the page.
+    results: Vec<TxSummary>,
+}
+
+/// The response from the `get_block_by_height` method.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Block {
+    /// Whether the block is part of the canonical chain.
+    pub canonical: bool,
+    /// The block height.
+    pub height: u64,
+    /// The block hash.
+    pub hash: String,
+    /// The hash of the parent block.
+    pub parent_block_hash: String,
+    /// The height of the anchoring burn block.
+    pub burn_block_height: u64,
+    /// The unix timestamp of the anchoring burn block.
+    pub burn_block_time: u64,
+    /// The ids of the transactions in the block.
+    pub txs: Vec<String>,
+}
+
+/// The response from the `get_pox_info` method.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct PoxInfo {
+    /// The identifier of the active `PoX` contract.
+    pub contract_id: String,
+    /// The burnchain block height `PoX` started at.
+    pub first_burnchain_block_height: u64,
+    /// The current burnchain block height.
+    pub current_burnchain_block_height: u64,
+    /// The current reward cycle id.
+    pub reward_cycle_id: u64,
+    /// The length of a reward cycle in burnchain blocks.
+    pub reward_cycle_length: u64,
+    /// The length of the prepare phase in burnchain blocks.
+    pub prepare_cycle_length: u64,
+    /// The minimum amount of micro-stacks required to stack in the next cycle.
+    pub min_amount_ustx: u64,
+    /// The number of burnchain blocks until the next reward cycle.
+    pub next_reward_cycle_in: u64,
+    /// The current reward cycle.
+    pub current_cycle: PoxCycle,
+    /// The next reward cycle.
+    pub next_cycle: PoxCycle,
+}
+
+/// A reward cycle of the `get_pox_info` method.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct PoxCycle {
+    /// The reward cycle id.
+    pub id: u64,
+    /// The minimum amount of micro-stacks required to stack in the cycle.
+    pub min_threshold_ustx: u64,
+    /// The amount of micro-stacks stacked in the cycle.
+    pub stacked_ustx: u64,
+    /// Whether `PoX` is active in the cycle.
+    pub is_pox_active: bool,
+}
+
+/// The response from the `get_mempool_transactions` method.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct MempoolPage {
+    /// The maximum number of transactions in the page.
+    pub limit: u64,
+    /// The offset of the page.
+    pub offset: u64,
+    /// The total number of transactions in the mempool.
+    pub total: u64,
+    /// The transactions in the page.
+    pub results: Vec<MempoolTransaction>,
+}
+
+/// A single mempool transaction object.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct MempoolTransaction {
+    /// The transaction id.
+    pub tx_id: String,
+    /// The transaction type.
+    pub tx_type: String,
+    /// The transaction fee rate in micro-stacks.
+    pub fee_rate: String,
+    /// The transaction nonce.
+    pub nonce: u64,
+    /// The sender address.
+    pub sender_address: String,
+    /// The transaction status.
+    pub tx_status: String,
+}
+
+/// The cache key of a read-only call.
+///
+/// Contract address, contract name, function name, hashed arguments & sender.
+type ReadOnlyKey = (String, String, String, [u8; 32], String);
+
+/// An in-memory TTL cache for successful read-only calls.
+#[derive(Debug)]
+struct ReadOnlyCache {
+    /// How long a cached response stays valid.
+    ttl: Duration,
+    /// The maximum number of cached responses.
+    capacity: usize,
+    /// The cached responses & their insertion time.
+    entries: Mutex<HashMap<ReadOnlyKey, (Instant, ReadOnlyResponse)>>,
+}
+
+impl ReadOnlyCache {
+    /// Creates a new `ReadOnlyCache`.
+    fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            ttl,
+            capacity,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Gets a cached response, if present & not expired.
+    fn get(&self, key: &ReadOnlyKey) -> Option<ReadOnlyResponse> {
+        let entries = self.entries.lock().ok()?;
+        let (inserted, response) = entries.get(key)?;
+
+        if inserted.elapsed() < self.ttl {
+            Some(response.clone())
+        } else {
+            None
+        }
+    }
+
+    /// Caches a response, evicting expired & then the oldest entries when full.
+    fn insert(&self, key: ReadOnlyKey, response: ReadOnlyResponse) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if let Ok(mut entries) = self.entries.lock() {
+            if entries.len() >= self.capacity && !entries.contains_key(&key) {
+                entries.retain(|_, (inserted, _)| inserted.elapsed() < self.ttl);
+            }
+
+            if entries.len() >= self.capacity && !entries.contains_key(&key) {
+                let oldest = entries
+                    .iter()
+                    .min_by_key(|(_, (inserted, _))| *inserted)
+                    .map(|(key, _)| key.clone());
+
+                if let Some(oldest) = oldest {
+                    entries.remove(&oldest);
+                }
+            }
+
+            entries.insert(key, (Instant::now(), response));
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct StacksRPC {
     /// The stacks rpc endpoint.
     __url: String,
+    /// The read-only call cache, shared between clones.
+    __cache: Option<Arc<ReadOnlyCache>>,
+    /// The http agent, its connection pool is shared between clones.
+    __agent: ureq::Agent,
 }
 
 impl StacksRPC {
@@ -189,27 +582,258 @@ impl StacksRPC {
     where
         T: Into<String>,
     {
-        Self { __url: url.into() }
+        let agent = ureq::AgentBuilder::new()
+            .timeout(DEFAULT_TIMEOUT)
+            .max_idle_connections_per_host(DEFAULT_MAX_IDLE_PER_HOST)
+            .build();
+
+        Self {
+            __url: url.into(),
+            __cache: None,
+            __agent: agent,
+        }
+    }
+
+    /// Uses a pre-configured `ureq::Agent` for all requests.
+    ///
+    /// Passing the same agent to multiple instances shares its connection pool.
+    #[must_use]
+    pub fn with_agent(mut self, agent: ureq::Agent) -> Self {
+        self.__agent = agent;
+        self
+    }
+
+    /// Sets the timeout of a request, replacing the agent with a fresh one.
+    ///
+    /// This discards an agent set with `with_agent`, including its connection
+    /// pool. To use both, set the timeout on the `ureq::AgentBuilder` instead.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.__agent = ureq::AgentBuilder::new()
+            .timeout(timeout)
+            .max_idle_connections_per_host(DEFAULT_MAX_IDLE_PER_HOST)
+            .build();
+        self
+    }
+
+    /// Enables caching of successful read-only calls.
+    ///
+    /// Identical calls (contract, function, arguments & sender) within `ttl`
+    /// are served from memory, at most `capacity` responses are kept.
+    #[must_use]
+    pub fn with_cache(mut self, ttl: Duration, capacity: usize) -> Self {
+        self.__cache = Some(Arc::new(ReadOnlyCache::new(ttl, capacity)));
+        self
     }
 
     /// Gets the node info.
     pub fn info(&self) -> Result<NodeInfoResponse, Error> {
-        let request = ureq::get(&f!("{}/v2/info", self.__url));
+        let request = self.__agent.get(&f!("{}/v2/info", self.__url));
         Ok(request.call()?.into_json::<NodeInfoResponse>()?)
     }
 
+    /// Gets the `PoX` info, i.e. the reward cycle length, current cycle & stacking thresholds.
+    pub fn get_pox_info(&self) -> Result<PoxInfo, Error> {
+        let request = self.__agent.get(&f!("{}/v2/pox", self.__url));
+        Ok(request.call()?.into_json::<PoxInfo>()?)
+    }
+
     /// Gets the info of a specific address.
     pub fn address<T>(&self, addr: T) -> Result<AddressInfoResponse, Error>
     where
         T: Into<String>,
     {
-        let request = ureq::get(&f!("{}/v2/accounts/{}", self.__url, addr.into()));
+        let request = self
+            .__agent
+            .get(&f!("{}/v2/accounts/{}", self.__url, addr.into()));
         Ok(request.call()?.into_json::<AddressInfoResponse>()?)
     }
 
+    /// Gets the next nonce of a specific address, including pending transactions.
+    pub fn fetch_next_nonce(&self, addr: &str) -> Result<u64, Error> {
+        let request = self
+            .__agent
+            .get(&f!("{}/extended/v1/address/{}/nonces", self.__url, addr));
+
+        let response = request.call()?.into_json::<NonceResponse>()?;
+        Ok(response.possible_next_nonce)
+    }
+
+    /// Gets the locked STX of an address & when it unlocks.
+    ///
+    /// Returns an empty list if nothing is locked.
+    pub fn fetch_stx_unlock_schedule(&self, addr: &str) -> Result<Vec<UnlockEvent>, Error> {
+        let request = self
+            .__agent
+            .get(&f!("{}/extended/v1/address/{}/stx", self.__url, addr));
+
+        let response = request.call()?.into_json::<StxBalanceResponse>()?;
+        let amount = response
+            .locked
+            .parse::<u128>()
+            .map_err(|_| Error::BadResponse("locked".to_string(), response.locked.clone()))?;
+
+        if amount == 0 {
+            return Ok(vec![]);
+        }
+
+        Ok(vec![UnlockEvent {
+            amount,
+            lock_tx_id: response.lock_tx_id,
+            lock_height: response.lock_height,
+            burnchain_lock_height: response.burnchain_lock_height,
+            burnchain_unlock_height: response.burnchain_unlock_height,
+        }])
+    }
+
+    /// Gets the source of a deployed contract.
+    pub fn get_contract_source(
+        &self,
+        contract_addr: &str,
+        contract_name: &str,
+    ) -> Result<ContractSource, Error> {
+        let request = self.__agent.get(&f!(
+            "{}/v2/contracts/source/{}/{}",
+            self.__url,
+            contract_addr,
+            contract_name
+        ));
+
+        Ok(request.call()?.into_json::<ContractSource>()?)
+    }
+
+    /// Gets an entry of a contract data map.
+    ///
+    /// Returns the decoded `(optional ...)` value, `none` if the key is absent.
+    pub fn get_map_entry(
+        &self,
+        contract_addr: &str,
+        contract_name: &str,
+        map_name: &str,
+        key: &dyn Clarity,
+    ) -> Result<Box<dyn Clarity>, Error> {
+        let request = self
+            .__agent
+            .post(&f!(
+                "{}/v2/map_entry/{}/{}/{}",
+                self.__url,
+                contract_addr,
+                contract_name,
+                map_name
+            ))
+            .query("proof", "0");
+
+        let response = request.send_json(ureq::json!(f!("0x{}", key.hex()?)))?;
+        let entry = response.into_json::<MapEntryResponse>()?;
+
+        let data = entry.data.trim_start_matches("0x");
+        Ok(decode_clarity_type(&hex_to_bytes(data)?)?)
+    }
+
+    /// Gets a page of confirmed transactions of a specific address.
+    ///
+    /// Optionally keeps only the transactions of a single `TxType`.
+    pub fn fetch_account_transactions(
+        &self,
+        addr: &str,
+        offset: u64,
+        limit: u64,
+        filter: Option<TxType>,
+    ) -> Result<Vec<TxSummary>, Error> {
+        let request = self
+            .__agent
+            .get(&f!(
+                "{}/extended/v1/address/{}/transactions",
+                self.__url,
+                addr
+            ))
+            .query("offset", &offset.to_string())
+            .query("limit", &limit.to_string());
+
+        let page = request.call()?.into_json::<TxSummaryPage>()?;
+        Ok(filter_transactions(page.results, filter))
+    }
+
+    /// Gets a block by its height.
+    pub fn get_block_by_height(&self, height: u64) -> Result<Block, Error> {
+        let request =
+            self.__agent
+                .get(&f!("{}/extended/v1/block/by_height/{}", self.__url, height));
+
+        Ok(request.call()?.into_json::<Block>()?)
+    }
+
+    /// Gets a page of the transactions of a block by its hash.
+    pub fn get_block_transactions(
+        &self,
+        hash: &str,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<TxSummary>, Error> {
+        let request = self
+            .__agent
+            .get(&f!("{}/extended/v1/tx/block/{}", self.__url, hash))
+            .query("offset", &offset.to_string())
+            .query("limit", &limit.to_string());
+
+        let page = request.call()?.into_json::<TxSummaryPage>()?;
+        Ok(page.results)
+    }
+
+    /// Gets a transaction, pending or confirmed, by its id.
+    pub fn fetch_transaction(&self, txid: &str) -> Result<TransactionInfo, Error> {
+        let request = self
+            .__agent
+            .get(&f!("{}/extended/v1/tx/{}", self.__url, txid));
+
+        Ok(request.call()?.into_json::<TransactionInfo>()?)
+    }
+
+    /// Polls a transaction every `poll_interval` until it is no longer pending.
+    ///
+    /// A transaction the API doesn't know yet, e.g. right after broadcasting,
+    /// responds with 404 & is treated as pending. Returns the final status, or
+    /// `Error::ConfirmationTimeout` if the transaction is still pending after `timeout`.
+    pub fn wait_for_confirmation(
+        &self,
+        txid: &str,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<TransactionStatus, Error> {
+        let start = Instant::now();
+
+        loop {
+            match self.fetch_transaction(txid) {
+                Ok(info) if info.tx_status != TransactionStatus::Pending => {
+                    return Ok(info.tx_status)
+                }
+                Ok(_) | Err(Error::Ureq(ureq::Error::Status(404, _))) => {}
+                Err(err) => return Err(err),
+            }
+
+            match timeout.checked_sub(start.elapsed()) {
+                Some(remaining) if !remaining.is_zero() => {
+                    std::thread::sleep(poll_interval.min(remaining));
+                }
+                _ => return Err(Error::ConfirmationTimeout(txid.to_string())),
+            }
+        }
+    }
+
+    /// Gets a page of pending transactions from the mempool.
+    pub fn get_mempool_transactions(&self, offset: u64, limit: u64) -> Result<MempoolPage, Error> {
+        let request = self
+            .__agent
+            .get(&f!("{}/extended/v1/tx/mempool", self.__url))
+            .query("offset", &offset.to_string())
+            .query("limit", &limit.to_string());
+
+        Ok(request.call()?.into_json::<MempoolPage>()?)
+    }
+
     /// Gets an estimated fee for a `Transaction`.
     pub fn estimate_fee(&self, transaction: &Transaction) -> Result<EstimateFeeResponse, Error> {
-        let response = ureq::post(&f!("{}/v2/fees/transaction", self.__url))
+        let response = self.__agent.post(&f!("{}/v2/fees/transaction", self.__url))
             .send_json(ureq::json!({"transaction_payload": transaction.payload.hex()?, "estimated_len": transaction.len()?}));
 
         match response {
@@ -224,9 +848,80 @@ impl StacksRPC {
         }
     }
 
+    /// Gets the minimum fee rate in micro-stacks per byte accepted by the node.
+    pub fn fetch_min_fee_rate(&self) -> Result<u64, Error> {
+        let request = self.__agent.get(&f!("{}/v2/fees/transfer", self.__url));
+        Ok(request.call()?.into_json::<u64>()?)
+    }
+
+    /// Gets an estimated fee in micro-stacks for a `Transaction`.
+    ///
+    /// The middle estimate is clamped to at least the encoded length times the
+    /// node's minimum fee rate. Fails with `Error::FeeEstimation` if the node
+    /// rejects the estimate or returns no estimations.
+    pub fn estimate_tx_fee(&self, transaction: &Transaction) -> Result<u64, Error> {
+        let min_rate = self.fetch_min_fee_rate()?;
+        let len = transaction.len()?;
+
+        let fee = match self.estimate_fee(transaction)? {
+            EstimateFeeResponse::Ok(fee) => fee,
+            EstimateFeeResponse::Err(err) => return Err(Error::FeeEstimation(err)),
+        };
+
+        let estimate = fee
+            .middle()
+            .map(|estimate| estimate.fee)
+            .ok_or_else(|| Error::FeeEstimation("no estimations returned".into()))?;
+
+        Ok(clamp_fee(estimate, len, min_rate))
+    }
+
+    /// Gets an estimated fee in micro-stacks for deploying a contract.
+    ///
+    /// The estimate is based on the encoded `SmartContractPayload` & the
+    /// length of a single-sig standard transaction carrying it, clamped like
+    /// `estimate_tx_fee` to at least that length times the node's minimum fee rate.
+    pub fn estimate_deploy_fee(&self, name: &str, source: &str) -> Result<u64, Error> {
+        let min_rate = self.fetch_min_fee_rate()?;
+        let payload = SmartContractPayload::new(name, source);
+        let estimated_len = payload.len()? + STANDARD_TX_OVERHEAD;
+
+        let response = self.__agent.post(&f!("{}/v2/fees/transaction", self.__url)).send_json(
+            ureq::json!({"transaction_payload": payload.hex()?, "estimated_len": estimated_len}),
+        );
+
+        let fee = match response {
+            Ok(res) => res.into_json::<FeeOk>()?,
+            Err(ureq::Error::Status(_, res)) => {
+                return Err(Error::FeeEstimation(res.into_string()?))
+            }
+            Err(err) => return Err(Error::Ureq(err)),
+        };
+
+        let estimate = fee
+            .middle()
+            .map(|estimate| estimate.fee)
+            .ok_or_else(|| Error::FeeEstimation("no estimations returned".into()))?;
+
+        Ok(clamp_fee(estimate, estimated_len, min_rate))
+    }
+
+    /// Fills the nonce & fee of an unsigned `Transaction` sent by `addr`.
+    ///
+    /// The nonce is the next nonce of `addr`, the fee is estimated via
+    /// `estimate_tx_fee`. Call this before signing, changing the nonce or fee
+    /// invalidates existing signatures.
+    pub fn prepare(&self, transaction: &mut Transaction, addr: &str) -> Result<(), Error> {
+        transaction.set_nonce(self.fetch_next_nonce(addr)?);
+        transaction.set_fee(self.estimate_tx_fee(transaction)?);
+        Ok(())
+    }
+
     /// Broadcasts an encoded transaction.
     pub fn broadcast(&self, transaction: &Transaction) -> Result<BroadcastResponse, Error> {
-        let response = ureq::post(&f!("{}/v2/transactions", self.__url))
+        let response = self
+            .__agent
+            .post(&f!("{}/v2/transactions", self.__url))
             .set("Content-Type", "application/octet-stream")
             .send_bytes(&transaction.encode()?);
 
@@ -243,16 +938,113 @@ impl StacksRPC {
         }
     }
 
+    /// Checks whether the node would accept a transaction, without broadcasting it.
+    ///
+    /// Returns `Error::Rejected` with the node's rejection reason on failure.
+    pub fn dry_run(&self, transaction: &Transaction) -> Result<(), Error> {
+        let response = self
+            .__agent
+            .post(&f!("{}/v2/transactions/test", self.__url))
+            .set("Content-Type", "application/octet-stream")
+            .send_bytes(&transaction.encode()?);
+
+        match response {
+            Ok(_) => Ok(()),
+            Err(ureq::Error::Status(_, res)) => Err(Error::Rejected(res.into_json()?)),
+            Err(err) => Err(Error::Ureq(err)),
+        }
+    }
+
     /// Calls a read-only function on a contract.
+    ///
+    /// Without a `sender`, the call is made from [`DEFAULT_READ_ONLY_SENDER`].
+    /// Functions depending on `tx-sender` need an explicit sender.
     pub fn read_only(
         &self,
         contract_addr: &str,
         contract_name: &str,
         fn_name: &str,
         fn_args: FnArguments,
-        sender: &str,
+        sender: Option<&str>,
     ) -> Result<ReadOnlyResponse, Error> {
-        let req = ureq::post(&f!(
+        let sender = sender.unwrap_or(DEFAULT_READ_ONLY_SENDER);
+        let key = match &self.__cache {
+            Some(cache) => {
+                let hash = Sha256Hash::from_slice(fn_args.encode()?).into_bytes();
+                let key = (
+                    contract_addr.to_string(),
+                    contract_name.to_string(),
+                    fn_name.to_string(),
+                    hash,
+                    sender.to_string(),
+                );
+
+                if let Some(response) = cache.get(&key) {
+                    return Ok(response);
+                }
+
+                Some(key)
+            }
+            None => None,
+        };
+
+        let response = self
+            .read_only_request(contract_addr, contract_name, fn_name, fn_args, sender)?
+            .into_json::<ReadOnlyResponse>()?;
+
+        if let (Some(cache), Some(key), ReadOnlyResponse::Ok(_)) = (&self.__cache, key, &response) {
+            cache.insert(key, response.clone());
+        }
+
+        Ok(response)
+    }
+
+    /// Calls a read-only function on a contract & returns its decoded result with
+    /// the runtime cost reported by the node.
+    ///
+    /// Calls are never cached. Current nodes don't report costs, in which case
+    /// `cost` is `None` & the raw JSON response can be inspected instead.
+    pub fn call_read_only_with_cost(
+        &self,
+        contract_addr: &str,
+        contract_name: &str,
+        fn_name: &str,
+        fn_args: FnArguments,
+        sender: Option<&str>,
+    ) -> Result<ReadOnlyWithCost, Error> {
+        let sender = sender.unwrap_or(DEFAULT_READ_ONLY_SENDER);
+        let raw = self
+            .read_only_request(contract_addr, contract_name, fn_name, fn_args, sender)?
+            .into_json::<serde_json::Value>()?;
+
+        let bad = |field: &str| Error::BadResponse(field.to_string(), raw.to_string());
+        let response =
+            serde_json::from_value::<ReadOnlyCostResponse>(raw.clone()).map_err(|_| bad("okay"))?;
+
+        if !response.okay {
+            return Err(Error::ReadOnly(response.cause.unwrap_or_default()));
+        }
+
+        let result = response.result.ok_or_else(|| bad("result"))?;
+        let value = decode_clarity_type(&hex_to_bytes(result.trim_start_matches("0x"))?)?;
+
+        Ok(ReadOnlyWithCost {
+            value,
+            cost: response.cost,
+            raw,
+        })
+    }
+
+    /// Sends a read-only call to the node.
+    fn read_only_request(
+        &self,
+        contract_addr: &str,
+        contract_name: &str,
+        fn_name: &str,
+        fn_args: FnArguments,
+        sender: &str,
+    ) -> Result<ureq::Response, Error> {
+        let req = self.__agent.post(&f!(
             "{}/v2/contracts/call-read/{}/{}/{}",
             self.__url,
             contract_addr,
@@ -265,11 +1057,835 @@ impl StacksRPC {
             .map(|a| a.hex())
             .collect::<Result<Vec<String>, _>>()?;
 
-        let response = req.send_json(ureq::json!({
+        Ok(req.send_json(ureq::json!({
             "sender": sender,
             "arguments": arguments,
-        }))?;
+        }))?)
+    }
+}
+
+/// Keeps only the transactions matching a `TxType`, if any.
+fn filter_transactions(results: Vec<TxSummary>, filter: Option<TxType>) -> Vec<TxSummary> {
+    match filter {
+        Some(typ) => results.into_iter().filter(|tx| tx.tx_type == typ).collect(),
+        None => results,
+    }
+}
+
+/// Clamps a fee estimate to at least `len * min_rate`.
+fn clamp_fee(estimate: u64, len: usize, min_rate: u64) -> u64 {
+    let min = (len as u64).saturating_mul(min_rate);
+    estimate.max(min)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufRead;
+    use std::io::BufReader;
+    use std::io::Read;
+    use std::io::Write;
+    use std::net::TcpListener;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    use super::*;
+    use crate::clarity::Cast;
+    use crate::clarity::OptionalNone;
+    use crate::clarity::OptionalSome;
+    use crate::clarity::PrincipalStandard;
+    use crate::clarity::UInt;
+    use crate::transaction::STXTokenTransfer;
+    use crate::transaction::StacksMainnet;
+    use crate::SecretKey;
+
+    /// Spawns a local server answering every request with `body` & counting the requests.
+    fn mock_server(body: &'static str) -> (String, Arc<AtomicUsize>) {
+        mock_server_sequence(vec![body])
+    }
+
+    /// Spawns a local server answering the n-th request with the n-th body, repeating
+    /// the last body once exhausted, & counting the requests.
+    fn mock_server_sequence(bodies: Vec<&'static str>) -> (String, Arc<AtomicUsize>) {
+        mock_server_responses(bodies.into_iter().map(|b| (200, b)).collect())
+    }
+
+    /// Spawns a local server answering the n-th request with the n-th status & body,
+    /// repeating the last response once exhausted, & counting the requests.
+    fn mock_server_responses(responses: Vec<(u16, &'static str)>) -> (String, Arc<AtomicUsize>) {
+        let (url, hits, _) = mock_server_recording(responses);
+        (url, hits)
+    }
+
+    /// The request bodies received by a mock server.
+    type RecordedRequests = Arc<Mutex<Vec<Vec<u8>>>>;
+
+    /// Like `mock_server_responses`, but also records the body of every request.
+    fn mock_server_recording(
+        responses: Vec<(u16, &'static str)>,
+    ) -> (String, Arc<AtomicUsize>, RecordedRequests) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = f!("http://{}", listener.local_addr().unwrap());
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&hits);
+        let requests = Arc::new(Mutex::new(vec![]));
+        let recorder = Arc::clone(&requests);
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut content_length = 0;
+
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+
+                    if line.trim().is_empty() {
+                        break;
+                    }
+
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                }
+
+                let mut request_body = vec![0; content_length];
+                reader.read_exact(&mut request_body).unwrap();
+                recorder.lock().unwrap().push(request_body);
+                let index = counter.fetch_add(1, Ordering::SeqCst);
+                let (status, body) = responses[index.min(responses.len() - 1)];
+
+                let response = f!(
+                    "HTTP/1.1 {} OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        (url, hits, requests)
+    }
+
+    #[test]
+    fn test_rpc_timeout() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = f!("http://{}", listener.local_addr().unwrap());
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let _stream = stream.unwrap();
+                std::thread::sleep(Duration::from_secs(1));
+            }
+        });
+
+        let rpc = StacksRPC::new(url).with_timeout(Duration::from_millis(1));
+        let err = rpc.info().unwrap_err();
+        assert!(matches!(err, Error::Ureq(ureq::Error::Transport(_))));
+    }
+
+    #[test]
+    fn test_rpc_get_map_entry() {
+        let addr = "SP3FGQ8Z7JY9BWYZ5WM53E0M9NK7WHJF0691NZ159";
+        let key = PrincipalStandard::new(addr.to_string());
+
+        let (url, hits) =
+            mock_server(r#"{"data": "0x0a0100000000000000000000000000000064", "proof": ""}"#);
+        let present = StacksRPC::new(url)
+            .get_map_entry(addr, "token", "balances", &key)
+            .unwrap();
+        assert_eq!(present.to_string(), "(some u100)");
+        assert!(present.cast_as::<OptionalSome>().is_ok());
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+        let (url, _) = mock_server(r#"{"data": "0x09", "proof": ""}"#);
+        let absent = StacksRPC::new(url)
+            .get_map_entry(addr, "token", "balances", &key)
+            .unwrap();
+        assert_eq!(absent.to_string(), "none");
+        assert!(absent.cast_as::<OptionalNone>().is_ok());
+    }
+
+    #[test]
+    fn test_rpc_wait_for_confirmation() {
+        let txid = "0x5e9f3933e358df6a73fec0d47ce3e1062c20812c129f5294e6f37a8d27c051d9";
+        let pending = r#"{
+            "tx_id": "0x5e9f3933e358df6a73fec0d47ce3e1062c20812c129f5294e6f37a8d27c051d9",
+            "tx_type": "token_transfer",
+            "tx_status": "pending"
+        }"#;
+        let success = r#"{
+            "tx_id": "0x5e9f3933e358df6a73fec0d47ce3e1062c20812c129f5294e6f37a8d27c051d9",
+            "tx_type": "token_transfer",
+            "tx_status": "success",
+            "block_height": 142001
+        }"#;
+
+        let (url, hits) = mock_server_sequence(vec![pending, pending, success]);
+        let rpc = StacksRPC::new(url);
+
+        let info = rpc.fetch_transaction(txid).unwrap();
+        assert_eq!(info.tx_status, TransactionStatus::Pending);
+        assert_eq!(info.block_height, None);
+
+        let status = rpc
+            .wait_for_confirmation(txid, Duration::from_millis(1), Duration::from_secs(5))
+            .unwrap();
+        assert_eq!(status, TransactionStatus::Success);
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+
+        let (url, hits) = mock_server_responses(vec![(404, "{}"), (404, "{}"), (200, success)]);
+        let status = StacksRPC::new(url)
+            .wait_for_confirmation(txid, Duration::from_millis(1), Duration::from_secs(5))
+            .unwrap();
+        assert_eq!(status, TransactionStatus::Success);
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+
+        let (url, _) = mock_server(pending);
+        let err = StacksRPC::new(url)
+            .wait_for_confirmation(txid, Duration::from_millis(1), Duration::from_millis(20))
+            .unwrap_err();
+        assert!(matches!(err, Error::ConfirmationTimeout(id) if id == txid));
+
+        let (url, _) = mock_server_responses(vec![(404, "{}")]);
+        let err = StacksRPC::new(url)
+            .wait_for_confirmation(txid, Duration::from_millis(1), Duration::from_millis(20))
+            .unwrap_err();
+        assert!(matches!(err, Error::ConfirmationTimeout(id) if id == txid));
+    }
+
+    #[test]
+    fn test_rpc_prepare() {
+        let nonces = r#"{
+            "last_mempool_tx_nonce": 6,
+            "last_executed_tx_nonce": 5,
+            "possible_next_nonce": 7,
+            "detected_missing_nonces": []
+        }"#;
+        let estimate = r#"{
+            "estimated_cost": {
+                "read_count": 0,
+                "read_length": 0,
+                "runtime": 0,
+                "write_count": 0,
+                "write_length": 0
+            },
+            "estimated_cost_scalar": 1,
+            "estimations": [
+                { "fee": 1, "fee_rate": 1.0 },
+                { "fee": 2, "fee_rate": 2.0 },
+                { "fee": 3, "fee_rate": 3.0 }
+            ]
+        }"#;
+
+        let (url, hits) = mock_server_sequence(vec![nonces, "2", estimate]);
+        let addr = "SP3FGQ8Z7JY9BWYZ5WM53E0M9NK7WHJF0691NZ159";
+
+        let mut tx = STXTokenTransfer::builder()
+            .recipient(PrincipalStandard::new(addr.to_string()))
+            .amount(12345)
+            .sender(SecretKey::from_slice(&[0x01; 32]).unwrap())
+            .network(StacksMainnet::new())
+            .build()
+            .transaction();
+
+        StacksRPC::new(url).prepare(&mut tx, addr).unwrap();
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+
+        let origin = tx.auth.origin();
+        assert_eq!(origin.nonce(), 7);
+        assert_eq!(origin.fee(), 2 * tx.len().unwrap() as u64);
+    }
+
+    #[test]
+    fn test_rpc_estimate_tx_fee() {
+        let estimate = r#"{
+            "estimated_cost": {
+                "read_count": 0,
+                "read_length": 0,
+                "runtime": 0,
+                "write_count": 0,
+                "write_length": 0
+            },
+            "estimated_cost_scalar": 1,
+            "estimations": [
+                { "fee": 1, "fee_rate": 1.0 },
+                { "fee": 4608, "fee_rate": 2.0 },
+                { "fee": 11520, "fee_rate": 5.0 }
+            ]
+        }"#;
+        let empty = r#"{ "estimated_cost_scalar": 1, "estimations": [] }"#;
+        let rejected = "Estimation could not be performed";
+
+        let addr = "SP3FGQ8Z7JY9BWYZ5WM53E0M9NK7WHJF0691NZ159";
+        let tx = STXTokenTransfer::builder()
+            .recipient(PrincipalStandard::new(addr.to_string()))
+            .amount(12345)
+            .sender(SecretKey::from_slice(&[0x01; 32]).unwrap())
+            .network(StacksMainnet::new())
+            .build()
+            .transaction();
+
+        let (url, _) = mock_server_responses(vec![
+            (200, "1"),
+            (200, estimate),
+            (200, "1"),
+            (200, empty),
+            (200, "1"),
+            (400, rejected),
+        ]);
+        let rpc = StacksRPC::new(url);
+
+        assert_eq!(rpc.estimate_tx_fee(&tx).unwrap(), 4608);
+        assert!(matches!(
+            rpc.estimate_tx_fee(&tx),
+            Err(Error::FeeEstimation(err)) if err == "no estimations returned"
+        ));
+        assert!(matches!(
+            rpc.estimate_tx_fee(&tx),
+            Err(Error::FeeEstimation(err)) if err == rejected
+        ));
+    }
+
+    #[test]
+    fn test_rpc_read_only_cache() {
+        let (url, hits) =
+            mock_server(r#"{"okay": true, "result": "0x0100000000000000000000000000000006"}"#);
+        let addr = "SP3FGQ8Z7JY9BWYZ5WM53E0M9NK7WHJF0691NZ159";
+        let args = || FnArguments::new(vec![Box::new(UInt::new(1))]);
+
+        let rpc = StacksRPC::new(url.clone()).with_cache(Duration::from_secs(30), 16);
+
+        let first = rpc
+            .read_only(addr, "token", "get-decimals", args(), Some(addr))
+            .unwrap();
+        let second = rpc
+            .clone()
+            .read_only(addr, "token", "get-decimals", args(), Some(addr))
+            .unwrap();
+        assert_eq!(first, second);
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+        rpc.read_only(addr, "token", "get-name", args(), Some(addr))
+            .unwrap();
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+
+        let uncached = StacksRPC::new(url);
+        uncached
+            .read_only(addr, "token", "get-decimals", args(), Some(addr))
+            .unwrap();
+        uncached
+            .read_only(addr, "token", "get-decimals", args(), Some(addr))
+            .unwrap();
+        assert_eq!(hits.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn test_rpc_read_only_default_sender() {
+        let (url, _, requests) = mock_server_recording(vec![(
+            200,
+            r#"{"okay": true, "result": "0x0100000000000000000000000000000006"}"#,
+        )]);
+        let addr = "SP3FGQ8Z7JY9BWYZ5WM53E0M9NK7WHJF0691NZ159";
+        let rpc = StacksRPC::new(url);
+
+        rpc.read_only(
+            addr,
+            "token",
+            "get-decimals",
+            FnArguments::new(vec![]),
+            None,
+        )
+        .unwrap();
+        rpc.read_only(
+            addr,
+            "token",
+            "get-decimals",
+            FnArguments::new(vec![]),
+            Some(addr),
+        )
+        .unwrap();
+
+        let requests = requests.lock().unwrap();
+        let senders = requests
+            .iter()
+            .map(|body| {
+                serde_json::from_slice::<serde_json::Value>(body).unwrap()["sender"].clone()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(senders, [DEFAULT_READ_ONLY_SENDER, addr]);
+    }
+
+    #[test]
+    fn test_rpc_read_only_cache_expiry_and_capacity() {
+        let response = ReadOnlyResponse::Ok(ReadOnlyOk {
+            okay: true,
+            result: "0x03".into(),
+        });
+        let key = |name: &str| {
+            (
+                String::new(),
+                String::new(),
+                name.to_string(),
+                [0; 32],
+                String::new(),
+            )
+        };
+
+        let cache = ReadOnlyCache::new(Duration::from_secs(30), 2);
+        cache.insert(key("a"), response.clone());
+        cache.insert(key("b"), response.clone());
+        cache.insert(key("c"), response.clone());
+        assert!(cache.get(&key("a")).is_none());
+        assert!(cache.get(&key("b")).is_some());
+        assert!(cache.get(&key("c")).is_some());
+
+        let expired = ReadOnlyCache::new(Duration::ZERO, 2);
+        expired.insert(key("a"), response);
+        assert!(expired.get(&key("a")).is_none());
+    }
+
+    #[test]
+    fn test_rpc_address_transactions_filter() {
+        let json = r#"{
+            "limit": 3,
+            "offset": 0,
+            "total": 3,
+            "results": [
+                {
+                    "tx_id": "0x5e9f3933e358df6a73fec0d47ce3e1062c20812c129f5294e6f37a8d27c051d9",
+                    "tx_type": "token_transfer",
+                    "tx_status": "success",
+                    "block_height": 142001,
+                    "burn_block_time": 1710000000,
+                    "fee_rate": "180"
+                },
+                {
+                    "tx_id": "0x8b4c3f0ac5a2a57d1f8d9cb8be4e4e8e4f6a2c0e0b1ac7f9d0e9c6f3b2a1d0c9",
+                    "tx_type": "contract_call",
+                    "tx_status": "abort_by_response",
+                    "block_height": 142005,
+                    "burn_block_time": 1710000600,
+                    "fee_rate": "3000"
+                },
+                {
+                    "tx_id": "0x1f2e3d4c5b6a79880716253443526170f1e2d3c4b5a69788796a5b4c3d2e1f00",
+                    "tx_type": "some_future_type",
+                    "tx_status": "success",
+                    "block_height": 142010,
+                    "burn_block_time": 1710001200
+                }
+            ]
+        }"#;
+
+        let page = serde_json::from_str::<TxSummaryPage>(json).unwrap();
+        assert_eq!(page.results.len(), 3);
+        assert_eq!(page.results[2].tx_type, TxType::Unknown);
+
+        let all = filter_transactions(page.results.clone(), None);
+        assert_eq!(all.len(), 3);
+
+        let calls = filter_transactions(page.results.clone(), Some(TxType::ContractCall));
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].tx_status, "abort_by_response");
+        assert_eq!(calls[0].block_height, 142_005);
+
+        let transfers = filter_transactions(page.results, Some(TxType::TokenTransfer));
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].burn_block_time, 1_710_000_000);
+    }
+
+    #[test]
+    fn test_rpc_tx_summary_dedup() {
+        use std::collections::HashSet;
+
+        let summary = |tx_id: &str| TxSummary {
+            tx_id: tx_id.into(),
+            tx_type: TxType::TokenTransfer,
+            tx_status: "success".into(),
+            block_height: 142_001,
+            burn_block_time: 1_710_000_000,
+        };
+
+        let mut seen = HashSet::new();
+        assert!(seen.insert(summary("0x5e9f")));
+        assert!(!seen.insert(summary("0x5e9f")));
+        assert_eq!(seen.len(), 1);
+
+        seen.insert(summary("0x8b4c"));
+        assert_eq!(seen.len(), 2);
+    }
+
+    #[test]
+    fn test_rpc_clamp_fee() {
+        assert_eq!(clamp_fee(1, 180, 1), 180);
+        assert_eq!(clamp_fee(0, 180, 2), 360);
+        assert_eq!(clamp_fee(4_608, 180, 1), 4_608);
+        assert_eq!(clamp_fee(0, usize::MAX, u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn test_rpc_contract_source_deserialize() {
+        let json = r#"{
+            "source": "(define-read-only (get-counter)\n  (ok u0))\n",
+            "publish_height": 51983,
+            "proof": "0x1234"
+        }"#;
+
+        let source = serde_json::from_str::<ContractSource>(json).unwrap();
+        assert_eq!(
+            source.source,
+            "(define-read-only (get-counter)\n  (ok u0))\n"
+        );
+        assert_eq!(source.publish_height, 51983);
+        assert_eq!(source.proof.as_deref(), Some("0x1234"));
+
+        let json = r#"{ "source": "(ok u1)", "publish_height": 1 }"#;
+        let source = serde_json::from_str::<ContractSource>(json).unwrap();
+        assert_eq!(source.proof, None);
+    }
+
+    #[test]
+    fn test_rpc_mempool_page_deserialize() {
+        let json = r#"{
+            "limit": 2,
+            "offset": 0,
+            "total": 1342,
+            "results": [
+                {
+                    "tx_id": "0x5e9f3933e358df6a73fec0d47ce3e1062c20812c129f5294e6f37a8d27c051d9",
+                    "nonce": 12,
+                    "fee_rate": "3000",
+                    "sender_address": "ST2G0KVR849MZHJ6YB4DCN8K5TRDVXF92A664PHXT",
+                    "sponsored": false,
+                    "post_condition_mode": "deny",
+                    "tx_status": "pending",
+                    "receipt_time": 1710000000,
+                    "tx_type": "token_transfer"
+                },
+                {
+                    "tx_id": "0x8b4c3f0ac5a2a57d1f8d9cb8be4e4e8e4f6a2c0e0b1ac7f9d0e9c6f3b2a1d0c9",
+                    "nonce": 4,
+                    "fee_rate": "180",
+                    "sender_address": "ST1PQHQKV0RJXZFY1DGX8MNSNYVE3VGZJSRTPGZGM",
+                    "sponsored": false,
+                    "post_condition_mode": "allow",
+                    "tx_status": "pending",
+                    "receipt_time": 1710000042,
+                    "tx_type": "contract_call"
+                }
+            ]
+        }"#;
+
+        let page = serde_json::from_str::<MempoolPage>(json).unwrap();
+        assert_eq!(page.total, 1342);
+        assert_eq!(page.results.len(), 2);
+        assert_eq!(page.results[0].fee_rate, "3000");
+        assert_eq!(page.results[0].tx_type, "token_transfer");
+        assert_eq!(page.results[1].nonce, 4);
+    }
+
+    #[test]
+    fn test_rpc_estimate_deploy_fee() {
+        let source = "(define-data-var counter uint u0)\n".repeat(64);
+        let payload = SmartContractPayload::new("counter", source.clone());
+        assert_eq!(payload.len().unwrap() + STANDARD_TX_OVERHEAD, 2_304);
+
+        let json = r#"{
+            "estimated_cost": {
+                "read_count": 0,
+                "read_length": 0,
+                "runtime": 0,
+                "write_count": 64,
+                "write_length": 2160
+            },
+            "estimated_cost_scalar": 4,
+            "cost_scalar_change_by_byte": 0.00476837158203125,
+            "estimations": [
+                { "fee": 2304, "fee_rate": 1.0 },
+                { "fee": 4608, "fee_rate": 2.0 },
+                { "fee": 11520, "fee_rate": 5.0 }
+            ]
+        }"#;
+
+        let (url, hits) = mock_server_responses(vec![(200, "1"), (200, json)]);
+        let fee = StacksRPC::new(url)
+            .estimate_deploy_fee("counter", &source)
+            .unwrap();
+        assert_eq!(fee, 4_608);
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+
+        // The middle estimate is below 2_304 bytes at 3 micro-stacks per byte.
+        let (url, _) = mock_server_responses(vec![(200, "3"), (200, json)]);
+        let fee = StacksRPC::new(url)
+            .estimate_deploy_fee("counter", &source)
+            .unwrap();
+        assert_eq!(fee, 6_912);
+
+        assert!(FeeOk {
+            estimations: vec![]
+        }
+        .middle()
+        .is_none());
+    }
+
+    #[test]
+    fn test_rpc_dry_run() {
+        let rejection = r#"{
+            "error": "transaction rejected",
+            "reason": "ConflictingNonceInMempool",
+            "txid": "0x0102"
+        }"#;
+
+        let (url, hits) = mock_server_responses(vec![(200, "\"0x0102\""), (400, rejection)]);
+        let rpc = StacksRPC::new(url);
+
+        let tx = STXTokenTransfer::builder()
+            .recipient(PrincipalStandard::new(
+                "SP3FGQ8Z7JY9BWYZ5WM53E0M9NK7WHJF0691NZ159".to_string(),
+            ))
+            .amount(12345)
+            .sender(SecretKey::from_slice(&[0x01; 32]).unwrap())
+            .network(StacksMainnet::new())
+            .build()
+            .transaction();
+
+        rpc.dry_run(&tx).unwrap();
+
+        match rpc.dry_run(&tx) {
+            Err(Error::Rejected(err)) => {
+                assert_eq!(err.reason, "ConflictingNonceInMempool");
+                assert_eq!(err.txid, "0x0102");
+            }
+            other => panic!("expected a rejection, received: {other:?}"),
+        }
+
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_rpc_stx_unlock_schedule() {
+        let locked = r#"{
+            "balance": "1000000000",
+            "total_sent": "0",
+            "total_received": "1000000000",
+            "total_fees_sent": "0",
+            "total_miner_rewards_received": "0",
+            "lock_tx_id": "0x7f6e1a3b0ab7a8f8c2d3b2f3e4a5b6c7d8e9f0a1b2c3d4e5f6a7b8c9d0e1f2a3",
+            "locked": "900000000",
+            "lock_height": 140251,
+            "burnchain_lock_height": 841380,
+            "burnchain_unlock_height": 845580
+        }"#;
+        let unlocked = r#"{
+            "balance": "1000000000",
+            "total_sent": "0",
+            "total_received": "1000000000",
+            "total_fees_sent": "0",
+            "total_miner_rewards_received": "0",
+            "lock_tx_id": "",
+            "locked": "0",
+            "lock_height": 0,
+            "burnchain_lock_height": 0,
+            "burnchain_unlock_height": 0
+        }"#;
+
+        let (url, _) = mock_server_sequence(vec![locked, unlocked, r#"{"locked": "abc"}"#]);
+        let rpc = StacksRPC::new(url);
+        let addr = "SP3FGQ8Z7JY9BWYZ5WM53E0M9NK7WHJF0691NZ159";
+
+        let schedule = rpc.fetch_stx_unlock_schedule(addr).unwrap();
+        assert_eq!(
+            schedule,
+            vec![UnlockEvent {
+                amount: 900_000_000,
+                lock_tx_id: "0x7f6e1a3b0ab7a8f8c2d3b2f3e4a5b6c7d8e9f0a1b2c3d4e5f6a7b8c9d0e1f2a3"
+                    .to_string(),
+                lock_height: 140_251,
+                burnchain_lock_height: 841_380,
+                burnchain_unlock_height: 845_580,
+            }]
+        );
+
+        assert!(rpc.fetch_stx_unlock_schedule(addr).unwrap().is_empty());
+        assert!(matches!(
+            rpc.fetch_stx_unlock_schedule(addr),
+            Err(Error::BadResponse(field, _)) if field == "locked"
+        ));
+    }
+
+    #[test]
+    fn test_rpc_read_only_with_cost() {
+        let with_cost = r#"{
+            "okay": true,
+            "result": "0x0100000000000000000000000000000006",
+            "cost": {
+                "read_count": 3,
+                "read_length": 1024,
+                "write_count": 0,
+                "write_length": 0,
+                "runtime": 48321
+            }
+        }"#;
+        let without_cost = r#"{"okay": true, "result": "0x0100000000000000000000000000000006"}"#;
+        let failed = r#"{"okay": false, "cause": "Unchecked(NoSuchPublicFunction)"}"#;
+
+        let (url, _) = mock_server_sequence(vec![with_cost, without_cost, failed]);
+        let rpc = StacksRPC::new(url);
+        let addr = "SP3FGQ8Z7JY9BWYZ5WM53E0M9NK7WHJF0691NZ159";
+        let call = || {
+            rpc.call_read_only_with_cost(
+                addr,
+                "token",
+                "get-decimals",
+                FnArguments::new(vec![]),
+                None,
+            )
+        };
+
+        let response = call().unwrap();
+        assert_eq!(response.value.cast::<UInt>().unwrap().into_value(), 6);
+        assert_eq!(
+            response.cost,
+            Some(RuntimeCost {
+                read_count: 3,
+                read_length: 1024,
+                write_count: 0,
+                write_length: 0,
+                runtime: 48321,
+            })
+        );
+        assert_eq!(response.raw["cost"]["runtime"], 48321);
+
+        let response = call().unwrap();
+        assert_eq!(response.value.cast::<UInt>().unwrap().into_value(), 6);
+        assert_eq!(response.cost, None);
+        assert_eq!(
+            response.raw,
+            serde_json::from_str::<serde_json::Value>(without_cost).unwrap()
+        );
+        assert!(matches!(
+            call(),
+            Err(Error::ReadOnly(cause)) if cause == "Unchecked(NoSuchPublicFunction)"
+        ));
+    }
+
+    #[test]
+    fn test_rpc_get_block() {
+        let block = r#"{
+            "canonical": true,
+            "height": 142001,
+            "hash": "0x8e4c7a0cda5d8f2b9a3c6f1d2e0b4a7c9d8e6f5a4b3c2d1e0f9a8b7c6d5e4f3a",
+            "index_block_hash": "0x1d2c3b4a5f6e7d8c9b0a1f2e3d4c5b6a7f8e9d0c1b2a3f4e5d6c7b8a9f0e1d2c",
+            "parent_block_hash": "0x3a4f5e6d7c8b9a0f1e2d3c4b5a69788796a5b4c3d2e1f00112233445566778899",
+            "burn_block_time": 1710000000,
+            "burn_block_time_iso": "2024-03-09T16:00:00.000Z",
+            "burn_block_hash": "0x00000000000000000001c5a0e9e4a3b2c1d0e9f8a7b6c5d4e3f2a1b0c9d8e7f6",
+            "burn_block_height": 834210,
+            "miner_txid": "0x4b5c6d7e8f9a0b1c2d3e4f5a6b7c8d9e0f1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c",
+            "txs": [
+                "0x5e9f3933e358df6a73fec0d47ce3e1062c20812c129f5294e6f37a8d27c051d9",
+                "0x8b4c3f0ac5a2a57d1f8d9cb8be4e4e8e4f6a2c0e0b1ac7f9d0e9c6f3b2a1d0c9"
+            ],
+            "execution_cost_read_count": 120,
+            "execution_cost_read_length": 48213,
+            "execution_cost_runtime": 210944,
+            "execution_cost_write_count": 8,
+            "execution_cost_write_length": 912
+        }"#;
+        let transactions = r#"{
+            "limit": 20,
+            "offset": 0,
+            "total": 2,
+            "results": [
+                {
+                    "tx_id": "0x5e9f3933e358df6a73fec0d47ce3e1062c20812c129f5294e6f37a8d27c051d9",
+                    "tx_type": "coinbase",
+                    "tx_status": "success",
+                    "block_height": 142001,
+                    "burn_block_time": 1710000000
+                },
+                {
+                    "tx_id": "0x8b4c3f0ac5a2a57d1f8d9cb8be4e4e8e4f6a2c0e0b1ac7f9d0e9c6f3b2a1d0c9",
+                    "tx_type": "contract_call",
+                    "tx_status": "success",
+                    "block_height": 142001,
+                    "burn_block_time": 1710000000
+                }
+            ]
+        }"#;
+
+        let (url, _) = mock_server_sequence(vec![block, transactions]);
+        let rpc = StacksRPC::new(url);
+
+        let block = rpc.get_block_by_height(142_001).unwrap();
+        assert!(block.canonical);
+        assert_eq!(block.height, 142_001);
+        assert_eq!(block.burn_block_height, 834_210);
+        assert_eq!(block.txs.len(), 2);
+        assert!(block.parent_block_hash.starts_with("0x3a4f"));
+
+        let txs = rpc.get_block_transactions(&block.hash, 0, 20).unwrap();
+        assert_eq!(txs.len(), block.txs.len());
+        assert_eq!(txs[0].tx_type, TxType::Coinbase);
+        assert!(txs.iter().zip(&block.txs).all(|(tx, id)| &tx.tx_id == id));
+    }
+
+    #[test]
+    fn test_rpc_get_pox_info() {
+        let body = r#"{
+            "contract_id": "SP000000000000000000002Q6VF78.pox-4",
+            "pox_activation_threshold_ustx": 700073322473389,
+            "first_burnchain_block_height": 666050,
+            "current_burnchain_block_height": 861452,
+            "prepare_phase_block_length": 100,
+            "reward_phase_block_length": 2000,
+            "reward_slots": 4000,
+            "rejection_fraction": null,
+            "total_liquid_supply_ustx": 1480003133327448,
+            "current_cycle": {
+                "id": 93,
+                "min_threshold_ustx": 110000000000,
+                "stacked_ustx": 428105312000000,
+                "is_pox_active": true
+            },
+            "next_cycle": {
+                "id": 94,
+                "min_threshold_ustx": 100000000000,
+                "min_increment_ustx": 185000391665,
+                "stacked_ustx": 397000000000000,
+                "prepare_phase_start_block_height": 861950,
+                "blocks_until_prepare_phase": 498,
+                "reward_phase_start_block_height": 862050,
+                "blocks_until_reward_phase": 598,
+                "ustx_until_pox_rejection": null,
+                "is_pox_active": true
+            },
+            "min_amount_ustx": 100000000000,
+            "prepare_cycle_length": 100,
+            "reward_cycle_id": 93,
+            "reward_cycle_length": 2100,
+            "rejection_votes_left_required": null,
+            "next_reward_cycle_in": 598,
+            "contract_versions": []
+        }"#;
+
+        let (url, hits) = mock_server(body);
+        let rpc = StacksRPC::new(url);
 
-        Ok(response.into_json::<ReadOnlyResponse>()?)
+        let pox = rpc.get_pox_info().unwrap();
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+        assert_eq!(pox.contract_id, "SP000000000000000000002Q6VF78.pox-4");
+        assert_eq!(pox.current_cycle.id, 93);
+        assert_eq!(pox.current_cycle.id, pox.reward_cycle_id);
+        assert_eq!(pox.next_cycle.id, 94);
+        assert_eq!(pox.reward_cycle_length, 2100);
+        assert_eq!(pox.current_cycle.min_threshold_ustx, 110_000_000_000);
+        assert_eq!(pox.min_amount_ustx, pox.next_cycle.min_threshold_ustx);
+        assert!(pox.current_cycle.is_pox_active);
     }
 }
@@ -57,6 +57,18 @@ pub trait SpendingCondition: Codec + DynClone + Debug {
     fn set_fee(&mut self, fee: u64);
     /// Sets the nonce on the condition.
     fn set_nonce(&mut self, nonce: u64);
+    /// Returns a JSON description of the condition.
+    ///
+    /// Defaults to the hex-encoded bytes, so implementors outside this crate
+    /// keep compiling when the `serde` feature is enabled.
+    #[cfg(feature = "serde")]
+    fn to_json(&self) -> serde_json::Value {
+        let hex = self
+            .encode()
+            .ok()
+            .map(|bytes| crate::crypto::bytes_to_hex(&bytes));
+        serde_json::json!({ "hex": hex })
+    }
 }
 
 clone_trait_object!(SpendingCondition);
@@ -189,25 +201,43 @@ impl Auth {
     pub fn is_sponsored(&self) -> bool {
         matches!(self, Self::Sponsored(_, _))
     }
+
+    /// Returns a JSON description of the `Auth`.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            Self::Standard(origin) => serde_json::json!({
+                "type": "standard",
+                "origin": origin.to_json(),
+            }),
+            Self::Sponsored(origin, sponsor) => serde_json::json!({
+                "type": "sponsored",
+                "origin": origin.to_json(),
+                "sponsor": sponsor.to_json(),
+            }),
+        }
+    }
 }
 
 impl Codec for Auth {
     fn encode(&self) -> Result<Vec<u8>, clarity::Error> {
         let mut buff = vec![];
+        self.encode_into(&mut buff)?;
+        Ok(buff)
+    }
 
+    fn encode_into(&self, out: &mut Vec<u8>) -> Result<(), clarity::Error> {
         match self {
             Self::Standard(origin) => {
-                buff.push(AUTH_TYPE_STANDARD);
-                buff.extend_from_slice(&origin.encode()?);
+                out.push(AUTH_TYPE_STANDARD);
+                origin.encode_into(out)
             }
             Self::Sponsored(origin, sponsor) => {
-                buff.push(AUTH_TYPE_SPONSORED);
-                buff.extend_from_slice(&origin.encode()?);
-                buff.extend_from_slice(&sponsor.encode()?);
+                out.push(AUTH_TYPE_SPONSORED);
+                origin.encode_into(out)?;
+                sponsor.encode_into(out)
             }
         }
-
-        Ok(buff)
     }
 
     #[allow(unused_variables)]
@@ -259,15 +289,20 @@ impl SpendingConditionStandard {
 impl Codec for SpendingConditionStandard {
     fn encode(&self) -> Result<Vec<u8>, clarity::Error> {
         let mut buff = vec![];
-        buff.push(self.mode as u8);
-        buff.extend_from_slice(self.signer.as_bytes());
-        buff.extend_from_slice(&self.nonce.to_be_bytes());
-        buff.extend_from_slice(&self.fee.to_be_bytes());
-        buff.push(AUTH_ENCODING_TYPE_PUBLIC_KEY);
-        buff.extend_from_slice(self.signature.as_bytes());
+        self.encode_into(&mut buff)?;
         Ok(buff)
     }
 
+    fn encode_into(&self, out: &mut Vec<u8>) -> Result<(), clarity::Error> {
+        out.push(self.mode as u8);
+        out.extend_from_slice(self.signer.as_bytes());
+        out.extend_from_slice(&self.nonce.to_be_bytes());
+        out.extend_from_slice(&self.fee.to_be_bytes());
+        out.push(AUTH_ENCODING_TYPE_PUBLIC_KEY);
+        out.extend_from_slice(self.signature.as_bytes());
+        Ok(())
+    }
+
     #[allow(unused_variables)]
     fn decode(bytes: &[u8]) -> Result<Self, clarity::Error>
     where
@@ -315,7 +350,7 @@ impl SpendingCondition for SpendingConditionStandard {
     }
 
     fn signatures(&self) -> u16 {
-        u16::from(self.signature != MessageSignature::default())
+        u16::from(!self.signature.is_empty())
     }
 
     fn req_signatures(&self) -> u16 {
@@ -341,6 +376,17 @@ impl SpendingCondition for SpendingConditionStandard {
     fn set_nonce(&mut self, nonce: u64) {
         self.nonce = nonce;
     }
+
+    #[cfg(feature = "serde")]
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "hash_mode": self.mode.to_string(),
+            "signer": self.signer.hex(),
+            "nonce": self.nonce,
+            "fee": self.fee,
+            "signature": bytes_to_hex(self.signature.as_bytes()),
+        })
+    }
 }
 
 impl Default for SpendingConditionStandard {
@@ -534,6 +580,31 @@ impl SpendingCondition for SpendingConditionMultiSig {
     fn set_nonce(&mut self, nonce: u64) {
         self.nonce = nonce;
     }
+
+    #[cfg(feature = "serde")]
+    fn to_json(&self) -> serde_json::Value {
+        let fields = self
+            .fields
+            .iter()
+            .map(|field| match field {
+                AuthField::PK(pk) => {
+                    serde_json::json!({ "public_key": bytes_to_hex(pk.serialize()) })
+                }
+                AuthField::MSG(msg) => {
+                    serde_json::json!({ "signature": bytes_to_hex(msg.as_bytes()) })
+                }
+            })
+            .collect::<Vec<_>>();
+
+        serde_json::json!({
+            "hash_mode": self.mode.to_string(),
+            "signer": self.signer.hex(),
+            "nonce": self.nonce,
+            "fee": self.fee,
+            "fields": fields,
+            "required": self.required,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -12,6 +12,7 @@ use secp256k1::SecretKey;
 use crate::clarity;
 use crate::clarity::Codec;
 use crate::crypto::c32::Mode;
+use crate::crypto::secp256k1_context;
 use crate::crypto::SignatureHash;
 use crate::transaction::auth::AUTH_TYPE_SPONSORED;
 use crate::transaction::auth::AUTH_TYPE_STANDARD;
@@ -23,18 +24,26 @@ use crate::transaction::Payload;
 use crate::transaction::PostConditionMode;
 use crate::transaction::PostConditions;
 use crate::transaction::SpendingCondition;
+use crate::transaction::SpendingConditionStandard;
 use crate::transaction::TransactionSigner;
 use crate::transaction::TransactionVersion;
 
+/// The default fee ceiling used by `Transaction::set_fee_checked`, 10 STX in micro-stacks.
+pub const DEFAULT_MAX_FEE: u64 = 10_000_000;
+
 /// The anchor mode of a transaction.
 #[repr(u8)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
 pub enum AnchorMode {
     /// The transaction must be included in an anchor block.
     Strict = 0x01,
     /// The transaction must be included in a micro block.
+    ///
+    /// Microblocks are no longer mined since Stacks 2.1, the variant is kept
+    /// for decoding older transactions only.
     Micro = 0x02,
     /// The transaction can be included in either an anchor or microblock.
+    #[default]
     Any = 0x03,
 }
 
@@ -55,6 +64,10 @@ pub struct Transaction {
     pub post_conditions: PostConditions,
     /// The payload of the transaction.
     pub payload: Box<dyn Payload>,
+    /// An off-chain note for bookkeeping.
+    ///
+    /// The note is never encoded, it does not affect the transaction bytes or id.
+    pub note: Option<String>,
 }
 
 impl Transaction {
@@ -76,11 +89,54 @@ impl Transaction {
             post_condition_mode,
             post_conditions,
             payload,
+            note: None,
         }
     }
 
+    /// Returns a JSON description of the decoded transaction, e.g. for logging.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> serde_json::Value {
+        let post_conditions = self
+            .post_conditions
+            .value()
+            .iter()
+            .map(|condition| condition.to_json())
+            .collect::<Vec<_>>();
+
+        serde_json::json!({
+            "version": format!("{:?}", self.version),
+            "chain_id": self.chain_id.value(),
+            "auth": self.auth.to_json(),
+            "anchor_mode": format!("{:?}", self.anchor_mode),
+            "post_condition_mode": format!("{:?}", self.post_condition_mode),
+            "post_conditions": post_conditions,
+            "payload": self.payload.to_json(),
+            "note": self.note,
+        })
+    }
+
+    /// Validates the transaction before broadcasting.
+    ///
+    /// Rejects `AnchorMode::Micro`, as transactions restricted to microblocks
+    /// are no longer mined, & post-conditions attached in `PostConditionMode::Allow`.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.anchor_mode == AnchorMode::Micro {
+            return Err(Error::DeprecatedAnchorMode(self.anchor_mode));
+        }
+
+        if self.post_condition_mode == PostConditionMode::Allow && !self.post_conditions.is_empty()
+        {
+            return Err(Error::AllowModeWithConditions(self.post_conditions.len()));
+        }
+
+        Ok(())
+    }
+
     /// Signs the transaction with a secret-key.
     ///
+    /// Signing is fully deterministic (RFC6979), signing the same transaction
+    /// twice yields byte-equal signatures.
+    ///
     /// Returns the signed transaction.
     pub fn sign(self, key: SecretKey) -> Result<Transaction, Error> {
         let mut signer = TransactionSigner::new(self)?;
@@ -88,6 +144,30 @@ impl Transaction {
         Ok(signer.transaction())
     }
 
+    /// Replaces the sponsor of a sponsored transaction with a freshly signed single-sig sponsor.
+    ///
+    /// The origin is kept intact & verified, only the sponsor condition is reset to the
+    /// new key, fee & nonce before re-signing, e.g. for bumping the sponsor fee.
+    ///
+    /// Returns the re-sponsored transaction.
+    pub fn responsor(
+        &self,
+        key: SecretKey,
+        fee: u64,
+        nonce: u64,
+        mode: Mode,
+    ) -> Result<Transaction, Error> {
+        if matches!(mode, Mode::P2SH | Mode::P2WSH) {
+            return Err(Error::BadSpendingConditionModification);
+        }
+
+        let pk = key.public_key(secp256k1_context());
+        let sponsor = SpendingConditionStandard::new(pk, fee, nonce, mode);
+        let mut signer = TransactionSigner::new_sponser(self, Box::new(sponsor))?;
+        signer.sign_sponsor(key)?;
+        Ok(signer.transaction())
+    }
+
     /// Returns the current `SignatureHash` of the transaction.
     pub fn hash(&self) -> Result<SignatureHash, Error> {
         let bytes = self.encode()?;
@@ -99,11 +179,66 @@ impl Transaction {
         self.auth.set_fee(fee);
     }
 
+    /// Sets the fee for the transaction, rejecting fees above `DEFAULT_MAX_FEE`.
+    pub fn set_fee_checked(&mut self, fee: u64) -> Result<(), Error> {
+        self.set_fee_capped(fee, DEFAULT_MAX_FEE)
+    }
+
+    /// Sets the fee for the transaction, rejecting fees above `max`.
+    pub fn set_fee_capped(&mut self, fee: u64, max: u64) -> Result<(), Error> {
+        if fee > max {
+            return Err(Error::FeeTooHigh(fee, max));
+        }
+
+        self.set_fee(fee);
+        Ok(())
+    }
+
     /// Sets the nonce for the transaction.
     pub fn set_nonce(&mut self, nonce: u64) {
         self.auth.set_nonce(nonce);
     }
 
+    /// Returns an unsigned clone of the transaction with a new fee, e.g. for replace-by-fee.
+    ///
+    /// The auth is reset, the nonce is kept.
+    #[must_use]
+    pub fn with_fee(&self, fee: u64) -> Self {
+        let mut tx = self.reset();
+        tx.set_fee(fee);
+        tx
+    }
+
+    /// Returns an unsigned clone of the transaction with a new nonce.
+    ///
+    /// The auth is reset, the fee is kept.
+    #[must_use]
+    pub fn with_nonce(&self, nonce: u64) -> Self {
+        let mut tx = self.reset();
+        tx.set_nonce(nonce);
+        tx
+    }
+
+    /// Encodes the transaction with empty signatures, keeping the origin fee & nonce.
+    ///
+    /// This is the pre-sign layout handed to co-signers, for a single-sig
+    /// transaction it has the same length as the signed encoding.
+    pub fn serialize_unsigned(&self) -> Result<Vec<u8>, Error> {
+        Ok(self.reset().encode()?)
+    }
+
+    /// Returns an unsigned clone of the transaction, keeping the origin fee & nonce.
+    fn reset(&self) -> Self {
+        let origin = self.auth.origin();
+        let (fee, nonce) = (origin.fee(), origin.nonce());
+
+        let mut tx = self.clone();
+        tx.auth = tx.auth.reset();
+        tx.set_fee(fee);
+        tx.set_nonce(nonce);
+        tx
+    }
+
     /// Verifies the transaction origin signatures.
     pub(crate) fn verify_origin(&self) -> Result<SignatureHash, Error> {
         self.auth.verify_origin(self.initial_hash()?)
@@ -170,18 +305,20 @@ impl Transaction {
 impl Codec for Transaction {
     fn encode(&self) -> Result<Vec<u8>, clarity::Error> {
         let mut buffer = vec![];
-
-        buffer.push(self.version as u8);
-        buffer.extend_from_slice(&(self.chain_id as u32).to_be_bytes());
-        buffer.extend_from_slice(&self.auth.encode()?);
-        buffer.push(self.anchor_mode as u8);
-        buffer.push(self.post_condition_mode as u8);
-        buffer.extend_from_slice(&self.post_conditions.encode()?);
-        buffer.extend_from_slice(&self.payload.encode()?);
-
+        self.encode_into(&mut buffer)?;
         Ok(buffer)
     }
 
+    fn encode_into(&self, out: &mut Vec<u8>) -> Result<(), clarity::Error> {
+        out.push(self.version as u8);
+        out.extend_from_slice(&self.chain_id.value().to_be_bytes());
+        self.auth.encode_into(out)?;
+        out.push(self.anchor_mode as u8);
+        out.push(self.post_condition_mode as u8);
+        self.post_conditions.encode_into(out)?;
+        self.payload.encode_into(out)
+    }
+
     #[allow(unused_variables)]
     fn decode(bytes: &[u8]) -> Result<Self, clarity::Error>
     where
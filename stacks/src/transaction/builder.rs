@@ -15,9 +15,11 @@ use crate::clarity;
 use crate::clarity::Clarity;
 use crate::clarity::FnArguments;
 use crate::crypto::c32::Address;
-use crate::crypto::c32::Mode;
+use crate::crypto::c32::SingleSigMode;
+use crate::crypto::secp256k1_context;
 use crate::transaction::AnchorMode;
 use crate::transaction::Auth;
+use crate::transaction::Error;
 use crate::transaction::Network;
 use crate::transaction::PostConditionMode;
 use crate::transaction::PostConditions;
@@ -65,6 +67,11 @@ where
     /// Defaults to an empty set of post conditions.
     #[builder(default = PostConditions::default())]
     pub post_conditions: PostConditions,
+    /// The hash mode of the sender's spending condition.
+    ///
+    /// Defaults to `SingleSigMode::P2PKH`.
+    #[builder(default = SingleSigMode::P2PKH)]
+    pub hash_mode: SingleSigMode,
     /// Whether or not the transaction is sponsored.
     ///
     /// Defaults to `false`.
@@ -77,11 +84,21 @@ where
     T: Clarity,
     N: Network,
 {
+    /// Returns the total micro-stacks leaving the sender's account, the amount plus the fee.
+    ///
+    /// Fails with `Error::CostOverflow` if the sum overflows a `u64`.
+    pub fn total_cost(&self) -> Result<u64, Error> {
+        self.amount
+            .checked_add(self.fee)
+            .ok_or(Error::CostOverflow(self.amount, self.fee))
+    }
+
     /// Consumes the token-transfer & returns a `Transaction`.
     pub fn transaction(self) -> Transaction {
-        let pk = self.sender.public_key(&secp256k1::Secp256k1::new());
+        let pk = self.sender.public_key(secp256k1_context());
         let payload = TokenTransferPayload::new(self.recipient, self.amount, self.memo);
-        let condition = SpendingConditionStandard::new(pk, self.fee, self.nonce, Mode::P2PKH);
+        let condition =
+            SpendingConditionStandard::new(pk, self.fee, self.nonce, self.hash_mode.into());
 
         let auth = if self.sponsored {
             unimplemented!("Sponsored transactions are not yet supported")
@@ -116,7 +133,24 @@ where
     /// The function name.
     pub fn_name: String,
     /// The function arguments.
+    ///
+    /// Defaults to no arguments, e.g. when the arguments are added with `arg_named`.
+    #[builder(default = FnArguments::new(vec![]))]
     pub fn_args: FnArguments,
+    /// The declared parameter names of the function, in order.
+    ///
+    /// Named arguments are ordered against these by `transaction`.
+    #[builder(default, setter(
+        transform = |params: &[&str]| params.iter().map(ToString::to_string).collect()
+    ))]
+    pub fn_params: Vec<String>,
+    /// The function arguments keyed by parameter name, added with `arg_named`.
+    #[builder(via_mutators, mutators(
+        pub fn arg_named(&mut self, name: impl Into<String>, value: impl Clarity) {
+            self.named_args = std::mem::take(&mut self.named_args).arg(name, value);
+        }
+    ))]
+    pub named_args: NamedArguments,
     /// The private key of the sender.
     pub sender: SecretKey,
     /// The network of the transaction.
@@ -142,24 +176,54 @@ where
     /// Defaults to an empty set of post conditions.
     #[builder(default = PostConditions::default())]
     pub post_conditions: PostConditions,
+    /// The hash mode of the sender's spending condition.
+    ///
+    /// Defaults to `SingleSigMode::P2PKH`.
+    #[builder(default = SingleSigMode::P2PKH)]
+    pub hash_mode: SingleSigMode,
     /// Whether or not the transaction is sponsored.
     ///
     /// Defaults to `false`.
     #[builder(default = false)]
     pub sponsored: bool,
+    /// An off-chain note kept on the returned `Transaction`, see `Transaction::note`.
+    ///
+    /// Defaults to `None`.
+    #[builder(default, setter(strip_option, into))]
+    pub note: Option<String>,
 }
 
 impl<N> STXContractCall<N>
 where
     N: Network,
 {
+    /// Returns the total micro-stacks leaving the sender's account, the fee.
+    ///
+    /// Transfers made by the called contract are not included, use post-conditions
+    /// to bound them.
+    pub fn total_cost(&self) -> u64 {
+        self.fee
+    }
+
     /// Consumes the contract-call & returns a `Transaction`.
+    ///
+    /// Named arguments are ordered by `fn_params`, failing on unknown, duplicate or
+    /// missing names, or if positional arguments were also given.
     pub fn transaction(self) -> Result<Transaction, clarity::Error> {
-        let pk = self.sender.public_key(&secp256k1::Secp256k1::new());
+        let pk = self.sender.public_key(secp256k1_context());
         let address = Address::from_str(&self.address)?;
 
-        let payload = ContractCallPayload::new(address, self.contract, self.fn_name, self.fn_args);
-        let condition = SpendingConditionStandard::new(pk, self.fee, self.nonce, Mode::P2PKH);
+        let fn_args = if self.named_args.is_empty() {
+            self.fn_args
+        } else if self.fn_args.is_empty() {
+            self.named_args.into_fn_args(&self.fn_params)?
+        } else {
+            return Err(clarity::Error::MixedArguments);
+        };
+
+        let payload = ContractCallPayload::new(address, self.contract, self.fn_name, fn_args)?;
+        let condition =
+            SpendingConditionStandard::new(pk, self.fee, self.nonce, self.hash_mode.into());
 
         let auth = if self.sponsored {
             unimplemented!("Sponsored transactions are not yet supported")
@@ -167,7 +231,7 @@ where
             Auth::Standard(Box::new(condition))
         };
 
-        let transaction = Transaction::new(
+        let mut transaction = Transaction::new(
             self.network.version(),
             self.network.chain_id(),
             auth,
@@ -176,7 +240,87 @@ where
             self.post_conditions,
             Box::new(payload),
         );
+        transaction.note = self.note;
 
         Ok(transaction)
     }
 }
+
+/// Contract-call arguments keyed by parameter name.
+///
+/// Converted into positional `FnArguments` following the declared parameter order
+/// of the function interface.
+#[derive(Debug, Clone, Default)]
+pub struct NamedArguments {
+    /// The named arguments, in insertion order.
+    args: Vec<(String, Box<dyn Clarity>)>,
+}
+
+impl PartialEq for NamedArguments {
+    fn eq(&self, other: &Self) -> bool {
+        self.args.len() == other.args.len()
+            && self
+                .args
+                .iter()
+                .zip(&other.args)
+                .all(|(a, b)| a.0 == b.0 && a.1.to_string() == b.1.to_string())
+    }
+}
+
+impl Eq for NamedArguments {}
+
+impl NamedArguments {
+    /// Creates a new, empty `NamedArguments`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a named argument.
+    #[must_use]
+    pub fn arg<S, T>(mut self, name: S, value: T) -> Self
+    where
+        S: Into<String>,
+        T: Clarity,
+    {
+        self.args.push((name.into(), Box::new(value)));
+        self
+    }
+
+    /// Returns `true` if no named arguments were added.
+    pub fn is_empty(&self) -> bool {
+        self.args.is_empty()
+    }
+
+    /// Orders the arguments by the declared parameter names of a function.
+    ///
+    /// Fails on unknown, duplicate or missing parameter names.
+    pub fn into_fn_args<S>(self, params: &[S]) -> Result<FnArguments, clarity::Error>
+    where
+        S: AsRef<str>,
+    {
+        let mut slots: Vec<Option<Box<dyn Clarity>>> = vec![None; params.len()];
+
+        for (name, value) in self.args {
+            let index = params
+                .iter()
+                .position(|param| param.as_ref() == name)
+                .ok_or_else(|| clarity::Error::UnknownArgument(name.clone()))?;
+
+            if slots[index].is_some() {
+                return Err(clarity::Error::DuplicateKey(name));
+            }
+
+            slots[index] = Some(value);
+        }
+
+        let values = slots
+            .into_iter()
+            .zip(params)
+            .map(|(slot, param)| {
+                slot.ok_or_else(|| clarity::Error::MissingArgument(param.as_ref().to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(FnArguments::new(values))
+    }
+}
@@ -8,11 +8,15 @@
 
 use std::fmt::Debug;
 use std::fmt::Display;
+use std::str::FromStr;
 
 use dyn_clone::clone_trait_object;
 use dyn_clone::DynClone;
 
 use crate::clarity;
+use crate::clarity::decode_clarity_type;
+use crate::clarity::impls::checked_read;
+use crate::clarity::impls::checked_slice;
 use crate::clarity::macros::impl_clarity_primitive;
 use crate::clarity::Cast;
 use crate::clarity::Clarity;
@@ -20,7 +24,12 @@ use crate::clarity::Codec;
 use crate::clarity::LengthPrefixedStr;
 use crate::clarity::PrincipalContract;
 use crate::clarity::PrincipalStandard;
+use crate::clarity::CLARITY_TYPE_CON_PR;
 use crate::clarity::CLARITY_TYPE_NON_STD;
+use crate::clarity::CLARITY_TYPE_STD_PR;
+use crate::crypto::c32_address;
+use crate::crypto::c32_address_decode;
+use crate::transaction::Error;
 
 /// The standard STX condition type.
 pub(crate) const POST_CONDITION_TYPE_STX: u8 = 0x00;
@@ -32,12 +41,15 @@ pub(crate) const POST_CONDITION_TYPE_NON_FUNGIBLE: u8 = 0x02;
 pub(crate) const POST_CONDITION_PRINCIPAL_STD: u8 = 0x02;
 /// The contract principal type.
 pub(crate) const POST_CONDITION_PRINCIPAL_CON: u8 = 0x03;
+/// The encoded size of the smallest post-condition, a standard principal STX condition.
+pub(crate) const POST_CONDITION_MIN_SIZE: usize = 32;
 
 /// Convenience macro for creating post-conditions.
 #[macro_export]
 macro_rules! post_condition {
     (@box $e:expr) => (Box::new($e) as Box<dyn $crate::transaction::Condition>);
     () => ($crate::transaction::PostConditions::new(Vec::new()));
+    (from $e:expr) => ($crate::transaction::PostConditions::new($e));
     ($(($type:ident, $($args:tt)*)),* $(,)?) => {{
         let mut tmp = Vec::new();
         $(tmp.push($crate::post_condition!(@gen $type, $($args)*));)*
@@ -49,8 +61,21 @@ macro_rules! post_condition {
     (@gen NonFungibleCondition, $($args:tt)*) => ($crate::post_condition!(@box $crate::transaction::NonFungiblePostCondition::new($($args)*)));
 }
 
-/// Marker trait for post-conditions.
-pub trait Condition: Codec + DynClone + Send + Sync + Debug {}
+/// Trait for post-conditions.
+pub trait Condition: Codec + DynClone + Send + Sync + Debug {
+    /// Returns a JSON description of the post-condition.
+    ///
+    /// Defaults to the hex-encoded bytes, so implementors outside this crate
+    /// keep compiling when the `serde` feature is enabled.
+    #[cfg(feature = "serde")]
+    fn to_json(&self) -> serde_json::Value {
+        let hex = self
+            .encode()
+            .ok()
+            .map(|bytes| crate::crypto::bytes_to_hex(&bytes));
+        serde_json::json!({ "hex": hex })
+    }
+}
 clone_trait_object!(Condition);
 
 /// The post-condition code.
@@ -73,13 +98,33 @@ pub enum ConditionCode {
     Has = 0x11,
 }
 
+impl TryFrom<u8> for ConditionCode {
+    type Error = clarity::Error;
+
+    fn try_from(byte: u8) -> Result<Self, Self::Error> {
+        match byte {
+            0x01 => Ok(Self::EQ),
+            0x02 => Ok(Self::GT),
+            0x03 => Ok(Self::GTE),
+            0x04 => Ok(Self::LT),
+            0x05 => Ok(Self::LTE),
+            0x10 => Ok(Self::HasNot),
+            0x11 => Ok(Self::Has),
+            _ => Err(clarity::Error::UnexpectedType(byte)),
+        }
+    }
+}
+
 /// The post-condition mode.
 #[repr(u8)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
 pub enum PostConditionMode {
     /// Allow mode, this turns off all post-conditions.
     Allow = 0x01,
     /// Deny mode, this turns on all post-conditions.
+    ///
+    /// The default, transfers not covered by a post-condition abort.
+    #[default]
     Deny = 0x02,
 }
 
@@ -89,24 +134,101 @@ impl_clarity_primitive!(
     CLARITY_TYPE_NON_STD
 );
 
+impl PostConditions {
+    /// Appends a condition.
+    pub fn push(&mut self, condition: Box<dyn Condition>) {
+        self.__value.push(condition);
+    }
+
+    /// Appends a condition & returns `self`, for chaining.
+    #[must_use]
+    pub fn with<T>(mut self, condition: T) -> Self
+    where
+        T: Condition + 'static,
+    {
+        self.push(Box::new(condition));
+        self
+    }
+
+    /// Returns the number of conditions.
+    ///
+    /// Use `Codec::len` for the encoded length in bytes.
+    pub fn len(&self) -> usize {
+        self.__value.len()
+    }
+
+    /// Returns `true` if there are no conditions.
+    pub fn is_empty(&self) -> bool {
+        self.__value.is_empty()
+    }
+
+    /// Returns a copy with the conditions in a canonical order.
+    ///
+    /// Conditions are sorted by their encoded bytes, i.e. by type, principal,
+    /// asset & code. The node signs & hashes the exact order of the conditions,
+    /// so this is only meant for comparison & deduplication.
+    pub fn canonicalize(&self) -> Result<Self, clarity::Error> {
+        let mut keyed = self
+            .__value
+            .iter()
+            .map(|condition| Ok((condition.encode()?, condition.clone())))
+            .collect::<Result<Vec<_>, clarity::Error>>()?;
+
+        keyed.sort_by(|(a, _), (b, _)| a.cmp(b));
+        Ok(Self::new(
+            keyed.into_iter().map(|(_, condition)| condition).collect(),
+        ))
+    }
+}
+
 impl Codec for PostConditions {
     fn encode(&self) -> Result<Vec<u8>, clarity::Error> {
         let mut buff = vec![];
-        buff.extend_from_slice(&u32::try_from(self.__value.len())?.to_be_bytes());
+        self.encode_into(&mut buff)?;
+        Ok(buff)
+    }
+
+    fn encode_into(&self, out: &mut Vec<u8>) -> Result<(), clarity::Error> {
+        out.extend_from_slice(&u32::try_from(self.__value.len())?.to_be_bytes());
 
         for value in &self.__value {
-            buff.extend_from_slice(&value.encode()?);
+            value.encode_into(out)?;
         }
 
-        Ok(buff)
+        Ok(())
     }
 
-    #[allow(unused_variables)]
     fn decode(bytes: &[u8]) -> Result<Self, clarity::Error>
     where
         Self: Sized,
     {
-        unimplemented!()
+        let len = checked_read(bytes, 0, 4)?;
+        let num = u32::from_be_bytes(len.try_into()?) as usize;
+        let mut remainder = &bytes[4..];
+
+        if num.saturating_mul(POST_CONDITION_MIN_SIZE) > remainder.len() {
+            return Err(clarity::Error::LengthMismatch {
+                declared: num,
+                available: remainder.len(),
+            });
+        }
+
+        let mut conditions = Vec::with_capacity(num);
+
+        for _ in 0..num {
+            let (condition, consumed) = decode_condition_type(remainder)?;
+            remainder = &remainder[consumed..];
+            conditions.push(condition);
+        }
+
+        Ok(Self::new(conditions))
+    }
+}
+
+/// Post-conditions are not a clarity value, `Clarity::size` is the encoded length.
+impl Clarity for PostConditions {
+    fn size(&self) -> usize {
+        self.encode().map_or(0, |bytes| bytes.len())
     }
 }
 
@@ -166,6 +288,19 @@ impl STXPostCondition {
             code,
         }
     }
+
+    /// Creates a new `STXPostCondition` from a standard or contract principal string.
+    pub fn from_address(
+        address: &str,
+        amount: u64,
+        code: ConditionCode,
+    ) -> Result<Self, clarity::Error> {
+        Ok(Self {
+            address: parse_condition_principal(address)?,
+            amount,
+            code,
+        })
+    }
 }
 
 impl Codec for STXPostCondition {
@@ -187,16 +322,43 @@ impl Codec for STXPostCondition {
         Ok(buff)
     }
 
-    #[allow(unused_variables)]
     fn decode(bytes: &[u8]) -> Result<Self, clarity::Error>
     where
         Self: Sized,
     {
-        unimplemented!()
+        let tag = checked_read(bytes, 0, 1)?[0];
+
+        if tag != POST_CONDITION_TYPE_STX {
+            return Err(clarity::Error::BadIdentifier(POST_CONDITION_TYPE_STX, tag));
+        }
+
+        let (address, mut offset) = decode_condition_principal(&bytes[1..])?;
+        offset += 1;
+
+        let code = ConditionCode::try_from(checked_read(bytes, offset, 1)?[0])?;
+        offset += 1;
+
+        let amount = u64::from_be_bytes(checked_read(bytes, offset, 8)?.try_into()?);
+
+        Ok(Self {
+            address,
+            amount,
+            code,
+        })
     }
 }
 
-impl Condition for STXPostCondition {}
+impl Condition for STXPostCondition {
+    #[cfg(feature = "serde")]
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "stx",
+            "principal": self.address.to_string(),
+            "code": format!("{:?}", self.code),
+            "amount": self.amount,
+        })
+    }
+}
 
 /// The post-condition for fungible tokens.
 #[derive(Debug, Clone)]
@@ -224,6 +386,21 @@ impl FungiblePostCondition {
             info,
         }
     }
+
+    /// Creates a new `FungiblePostCondition` from a standard or contract principal string.
+    pub fn from_address(
+        address: &str,
+        amount: u64,
+        code: ConditionCode,
+        info: AssetInfo,
+    ) -> Result<Self, clarity::Error> {
+        Ok(Self {
+            address: parse_condition_principal(address)?,
+            amount,
+            code,
+            info,
+        })
+    }
 }
 
 impl Codec for FungiblePostCondition {
@@ -246,16 +423,51 @@ impl Codec for FungiblePostCondition {
         Ok(buff)
     }
 
-    #[allow(unused_variables)]
     fn decode(bytes: &[u8]) -> Result<Self, clarity::Error>
     where
         Self: Sized,
     {
-        unimplemented!()
+        let tag = checked_read(bytes, 0, 1)?[0];
+
+        if tag != POST_CONDITION_TYPE_FUNGIBLE {
+            return Err(clarity::Error::BadIdentifier(
+                POST_CONDITION_TYPE_FUNGIBLE,
+                tag,
+            ));
+        }
+
+        let (address, mut offset) = decode_condition_principal(&bytes[1..])?;
+        offset += 1;
+
+        let info = AssetInfo::decode(&bytes[offset..])?;
+        offset += info.len()?;
+
+        let code = ConditionCode::try_from(checked_read(bytes, offset, 1)?[0])?;
+        offset += 1;
+
+        let amount = u64::from_be_bytes(checked_read(bytes, offset, 8)?.try_into()?);
+
+        Ok(Self {
+            address,
+            amount,
+            code,
+            info,
+        })
     }
 }
 
-impl Condition for FungiblePostCondition {}
+impl Condition for FungiblePostCondition {
+    #[cfg(feature = "serde")]
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "fungible",
+            "principal": self.address.to_string(),
+            "code": format!("{:?}", self.code),
+            "amount": self.amount,
+            "asset": self.info.to_string(),
+        })
+    }
+}
 
 /// The post-condition for non-fungible tokens.
 #[derive(Debug, Clone)]
@@ -284,6 +496,24 @@ impl NonFungiblePostCondition {
             info,
         }
     }
+
+    /// Creates a new `NonFungiblePostCondition` from a standard or contract principal string.
+    pub fn from_address<S>(
+        address: &str,
+        name: S,
+        code: ConditionCode,
+        info: AssetInfo,
+    ) -> Result<Self, clarity::Error>
+    where
+        S: Clarity,
+    {
+        Ok(Self {
+            address: parse_condition_principal(address)?,
+            name: Box::new(name),
+            code,
+            info,
+        })
+    }
 }
 
 impl Codec for NonFungiblePostCondition {
@@ -306,16 +536,51 @@ impl Codec for NonFungiblePostCondition {
         Ok(buff)
     }
 
-    #[allow(unused_variables)]
     fn decode(bytes: &[u8]) -> Result<Self, clarity::Error>
     where
         Self: Sized,
     {
-        unimplemented!()
+        let tag = checked_read(bytes, 0, 1)?[0];
+
+        if tag != POST_CONDITION_TYPE_NON_FUNGIBLE {
+            return Err(clarity::Error::BadIdentifier(
+                POST_CONDITION_TYPE_NON_FUNGIBLE,
+                tag,
+            ));
+        }
+
+        let (address, mut offset) = decode_condition_principal(&bytes[1..])?;
+        offset += 1;
+
+        let info = AssetInfo::decode(&bytes[offset..])?;
+        offset += info.len()?;
+
+        let name = decode_clarity_type(&bytes[offset..])?;
+        offset += name.len()?;
+
+        let code = ConditionCode::try_from(checked_read(bytes, offset, 1)?[0])?;
+
+        Ok(Self {
+            address,
+            name,
+            code,
+            info,
+        })
     }
 }
 
-impl Condition for NonFungiblePostCondition {}
+impl Condition for NonFungiblePostCondition {
+    #[cfg(feature = "serde")]
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "non_fungible",
+            "principal": self.address.to_string(),
+            "code": format!("{:?}", self.code),
+            "asset": self.info.to_string(),
+            "name": self.name.to_string(),
+        })
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AssetInfo {
@@ -340,6 +605,37 @@ impl AssetInfo {
     }
 }
 
+impl Display for AssetInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}.{}::{}",
+            self.address.value(),
+            self.name.value(),
+            self.asset.value()
+        )
+    }
+}
+
+impl FromStr for AssetInfo {
+    type Err = Error;
+
+    /// Parses an asset identifier of the form `address.contract::asset`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bad_format = || Error::BadAssetFormat(s.to_string());
+
+        let (contract, asset) = s.split_once("::").ok_or_else(bad_format)?;
+        let (address, name) = contract.split_once('.').ok_or_else(bad_format)?;
+
+        if [address, name, asset].iter().any(|part| part.is_empty()) {
+            return Err(bad_format());
+        }
+
+        c32_address_decode(address).map_err(clarity::Error::from)?;
+        Ok(Self::new(address, name, asset))
+    }
+}
+
 impl Codec for AssetInfo {
     fn encode(&self) -> Result<Vec<u8>, clarity::Error> {
         let mut buff = vec![];
@@ -349,12 +645,72 @@ impl Codec for AssetInfo {
         Ok(buff)
     }
 
-    #[allow(unused_variables)]
     fn decode(bytes: &[u8]) -> Result<Self, clarity::Error>
     where
         Self: Sized,
     {
-        unimplemented!()
+        let prefix = checked_read(bytes, 0, 21)?;
+        let address = PrincipalStandard::new(c32_address(&prefix[1..], prefix[0])?);
+        let mut offset = 21;
+
+        let name = LengthPrefixedStr::decode(&bytes[offset..])?;
+        offset += name.len()?;
+
+        let asset = LengthPrefixedStr::decode(&bytes[offset..])?;
+
+        Ok(Self {
+            address,
+            name,
+            asset,
+        })
+    }
+}
+
+/// Decodes a post-condition from encoded bytes.
+///
+/// Returns the condition & the number of bytes consumed.
+pub fn decode_condition_type(bytes: &[u8]) -> Result<(Box<dyn Condition>, usize), clarity::Error> {
+    let tag = checked_read(bytes, 0, 1)?[0];
+
+    let condition: Box<dyn Condition> = match tag {
+        POST_CONDITION_TYPE_STX => Box::new(STXPostCondition::decode(bytes)?),
+        POST_CONDITION_TYPE_FUNGIBLE => Box::new(FungiblePostCondition::decode(bytes)?),
+        POST_CONDITION_TYPE_NON_FUNGIBLE => Box::new(NonFungiblePostCondition::decode(bytes)?),
+        _ => return Err(clarity::Error::UnexpectedType(tag)),
+    };
+
+    let consumed = condition.len()?;
+    Ok((condition, consumed))
+}
+
+/// Parses a standard (`SP...`) or contract (`SP....contract`) principal string.
+fn parse_condition_principal(address: &str) -> Result<Box<dyn Clarity>, clarity::Error> {
+    if let Some((addr, name)) = address.split_once('.') {
+        c32_address_decode(addr)?;
+        Ok(Box::new(PrincipalContract::from((addr, name))))
+    } else {
+        c32_address_decode(address)?;
+        Ok(Box::new(PrincipalStandard::from(address)))
+    }
+}
+
+/// Decodes the principal of a post-condition.
+///
+/// Returns the principal & the number of bytes consumed.
+fn decode_condition_principal(bytes: &[u8]) -> Result<(Box<dyn Clarity>, usize), clarity::Error> {
+    match checked_read(bytes, 0, 1)?[0] {
+        POST_CONDITION_PRINCIPAL_STD => {
+            let mut buff = vec![CLARITY_TYPE_STD_PR];
+            buff.extend_from_slice(checked_read(bytes, 1, 21)?);
+            Ok((Box::new(PrincipalStandard::decode(&buff)?), 22))
+        }
+        POST_CONDITION_PRINCIPAL_CON => {
+            let name_len = checked_read(bytes, 22, 1)?[0] as usize;
+            let mut buff = vec![CLARITY_TYPE_CON_PR];
+            buff.extend_from_slice(checked_slice(bytes, 1, 22 + name_len)?);
+            Ok((Box::new(PrincipalContract::decode(&buff)?), 23 + name_len))
+        }
+        byte => Err(clarity::Error::UnexpectedType(byte)),
     }
 }
 
@@ -405,6 +761,203 @@ mod tests {
         assert_eq!(hex, expected);
     }
 
+    #[test]
+    fn test_transaction_conditions_decode_huge_count() {
+        let mut bytes = u32::MAX.to_be_bytes().to_vec();
+        bytes.extend_from_slice(&[0; 40]);
+
+        let err = PostConditions::decode(&bytes).unwrap_err();
+        assert!(matches!(
+            err,
+            clarity::Error::LengthMismatch {
+                declared: 4_294_967_295,
+                available: 40
+            }
+        ));
+
+        assert!(PostConditions::decode(&[0, 0]).is_err());
+        assert!(PostConditions::decode(&[0, 0, 0, 0]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_transaction_conditions_decode_truncated() {
+        let (addr, name, info) = get_test_data();
+
+        let conditions: [Box<dyn Condition>; 4] = [
+            Box::new(STXPostCondition::new(
+                clarity!(PrincipalStandard, addr),
+                1_000_000,
+                ConditionCode::GTE,
+            )),
+            Box::new(STXPostCondition::new(
+                clarity!(PrincipalContract, addr, name),
+                1_000_000,
+                ConditionCode::GTE,
+            )),
+            Box::new(FungiblePostCondition::new(
+                clarity!(PrincipalContract, addr, name),
+                1_000_000,
+                ConditionCode::GTE,
+                info.clone(),
+            )),
+            Box::new(NonFungiblePostCondition::new(
+                clarity!(PrincipalStandard, addr),
+                clarity!(UInt, 1),
+                ConditionCode::Has,
+                info.clone(),
+            )),
+        ];
+
+        for condition in &conditions {
+            let bytes = condition.encode().unwrap();
+            for len in 0..bytes.len() {
+                assert!(decode_condition_type(&bytes[..len]).is_err());
+            }
+            assert_eq!(decode_condition_type(&bytes).unwrap().1, bytes.len());
+
+            let mut list = 1u32.to_be_bytes().to_vec();
+            list.extend_from_slice(&bytes);
+            for len in 0..list.len() {
+                assert!(PostConditions::decode(&list[..len]).is_err());
+            }
+        }
+
+        let bytes = info.encode().unwrap();
+        for len in 0..bytes.len() {
+            assert!(AssetInfo::decode(&bytes[..len]).is_err());
+        }
+        assert_eq!(AssetInfo::decode(&bytes).unwrap(), info);
+
+        let err = decode_condition_type(&[POST_CONDITION_TYPE_STX]).unwrap_err();
+        assert!(matches!(
+            err,
+            clarity::Error::UnexpectedEnd {
+                expected: 1,
+                available: 0
+            }
+        ));
+
+        let mut bytes = vec![
+            0,
+            0,
+            0,
+            1,
+            POST_CONDITION_TYPE_STX,
+            POST_CONDITION_PRINCIPAL_CON,
+        ];
+        bytes.extend_from_slice(&[0xff; 31]);
+        let err = PostConditions::decode(&bytes).unwrap_err();
+        assert!(matches!(err, clarity::Error::LengthMismatch { .. }));
+    }
+
+    #[test]
+    fn test_transaction_conditions_canonicalize() {
+        let (addr, name, info) = get_test_data();
+
+        let stx = post_condition!(
+            STXCondition,
+            clarity!(PrincipalStandard, addr),
+            1_000_000,
+            ConditionCode::GTE
+        );
+        let stx_contract = post_condition!(
+            STXCondition,
+            clarity!(PrincipalContract, addr, name),
+            1_000_000,
+            ConditionCode::LTE
+        );
+        let ft = post_condition!(
+            FungibleCondition,
+            clarity!(PrincipalStandard, addr),
+            500,
+            ConditionCode::EQ,
+            info.clone()
+        );
+        let nft = post_condition!(
+            NonFungibleCondition,
+            clarity!(PrincipalStandard, addr),
+            clarity!(UInt, 1),
+            ConditionCode::HasNot,
+            info
+        );
+
+        let a = PostConditions::new(vec![
+            nft.clone(),
+            stx_contract.clone(),
+            ft.clone(),
+            stx.clone(),
+        ]);
+        let b = PostConditions::new(vec![ft, stx, nft, stx_contract]);
+        assert_ne!(a.encode().unwrap(), b.encode().unwrap());
+
+        let canonical_a = a.canonicalize().unwrap();
+        let canonical_b = b.canonicalize().unwrap();
+        assert_eq!(canonical_a.encode().unwrap(), canonical_b.encode().unwrap());
+        assert_eq!(
+            canonical_a.canonicalize().unwrap().encode().unwrap(),
+            canonical_a.encode().unwrap()
+        );
+
+        let types = canonical_a
+            .into_iter()
+            .map(|condition| condition.encode().unwrap()[0])
+            .collect::<Vec<_>>();
+        assert_eq!(
+            types,
+            [
+                POST_CONDITION_TYPE_STX,
+                POST_CONDITION_TYPE_STX,
+                POST_CONDITION_TYPE_FUNGIBLE,
+                POST_CONDITION_TYPE_NON_FUNGIBLE
+            ]
+        );
+        assert_eq!(a.len(), 4);
+    }
+
+    #[test]
+    fn test_transaction_conditions_decode_roundtrip() {
+        let (addr, name, info) = get_test_data();
+
+        let conditions = post_condition!(
+            (
+                STXCondition,
+                clarity!(PrincipalStandard, addr),
+                1_000_000,
+                ConditionCode::GTE
+            ),
+            (
+                STXCondition,
+                clarity!(PrincipalContract, addr, name),
+                1_000_000,
+                ConditionCode::LT
+            ),
+            (
+                NonFungibleCondition,
+                clarity!(PrincipalStandard, addr),
+                clarity!(UInt, 60149),
+                ConditionCode::HasNot,
+                info.clone()
+            ),
+            (
+                FungibleCondition,
+                clarity!(PrincipalContract, addr, name),
+                1_000_000,
+                ConditionCode::EQ,
+                info.clone()
+            )
+        );
+
+        let encoded = conditions.encode().unwrap();
+        let decoded = PostConditions::decode(&encoded).unwrap();
+        assert_eq!(decoded.encode().unwrap(), encoded);
+
+        let (condition, consumed) = decode_condition_type(&encoded[4..]).unwrap();
+        assert_eq!(consumed, condition.len().unwrap());
+        assert_eq!(condition.encode().unwrap(), encoded[4..4 + consumed]);
+
+        assert!(decode_condition_type(&[0x09]).is_err());
+    }
+
     #[test]
     fn test_transaction_conditions_stx_encode() {
         let (addr, name, _) = get_test_data();
@@ -430,6 +983,94 @@ mod tests {
         assert_eq!(con_pc_hex, con_pc_expected)
     }
 
+    #[test]
+    fn test_transaction_conditions_incremental() {
+        let (addr, name, info) = get_test_data();
+
+        let stx = STXPostCondition::new(
+            clarity!(PrincipalStandard, addr),
+            1_000_000,
+            ConditionCode::GTE,
+        );
+        let ft = FungiblePostCondition::new(
+            clarity!(PrincipalContract, addr, name),
+            1_000_000,
+            ConditionCode::EQ,
+            info.clone(),
+        );
+        let nft = NonFungiblePostCondition::new(
+            clarity!(PrincipalStandard, addr),
+            clarity!(UInt, 60149),
+            ConditionCode::HasNot,
+            info,
+        );
+
+        let all = PostConditions::new(vec![
+            Box::new(stx.clone()) as Box<dyn Condition>,
+            Box::new(ft.clone()),
+            Box::new(nft.clone()),
+        ]);
+
+        let mut pushed = PostConditions::default();
+        assert!(pushed.is_empty());
+        pushed.push(Box::new(stx.clone()));
+        pushed.push(Box::new(ft.clone()));
+        pushed.push(Box::new(nft.clone()));
+
+        let chained = PostConditions::default().with(stx).with(ft).with(nft);
+
+        assert_eq!(pushed.len(), 3);
+        assert_eq!(chained.len(), 3);
+        assert!(!chained.is_empty());
+        assert_eq!(pushed.encode().unwrap(), all.encode().unwrap());
+        assert_eq!(chained.encode().unwrap(), all.encode().unwrap());
+    }
+
+    #[test]
+    fn test_transaction_conditions_from_address() {
+        let (addr, name, info) = get_test_data();
+
+        let std_pc = STXPostCondition::from_address(&addr, 1_000_000, ConditionCode::GTE).unwrap();
+        let std_pc_expected = "000216a5d9d331000f5b79578ce56bd157f29a9056f0d60300000000000f4240";
+        assert_eq!(std_pc.hex().unwrap(), std_pc_expected);
+
+        let principal = format!("{addr}.{name}");
+        let con_pc =
+            STXPostCondition::from_address(&principal, 1_000_000, ConditionCode::GTE).unwrap();
+        let con_pc_expected =
+            "000316a5d9d331000f5b79578ce56bd157f29a9056f0d604746573740300000000000f4240";
+        assert_eq!(con_pc.hex().unwrap(), con_pc_expected);
+
+        let ft_pc =
+            FungiblePostCondition::from_address(&addr, 1_000_000, ConditionCode::EQ, info.clone())
+                .unwrap();
+        let ft_pc_expected = FungiblePostCondition::new(
+            clarity!(PrincipalStandard, addr),
+            1_000_000,
+            ConditionCode::EQ,
+            info.clone(),
+        );
+        assert_eq!(ft_pc.encode().unwrap(), ft_pc_expected.encode().unwrap());
+
+        let nft_pc = NonFungiblePostCondition::from_address(
+            &principal,
+            clarity!(UInt, 60149),
+            ConditionCode::Has,
+            info.clone(),
+        )
+        .unwrap();
+        let nft_pc_expected = NonFungiblePostCondition::new(
+            clarity!(PrincipalContract, addr, name),
+            clarity!(UInt, 60149),
+            ConditionCode::Has,
+            info.clone(),
+        );
+        assert_eq!(nft_pc.encode().unwrap(), nft_pc_expected.encode().unwrap());
+
+        assert!(STXPostCondition::from_address("invalid", 1, ConditionCode::EQ).is_err());
+        assert!(STXPostCondition::from_address("invalid.contract", 1, ConditionCode::EQ).is_err());
+    }
+
     #[test]
     fn test_transaction_conditions_ft_encode() {
         let (addr, name, info) = get_test_data();
@@ -490,6 +1131,26 @@ mod tests {
         assert_eq!(hex, expected);
     }
 
+    #[test]
+    fn test_transaction_conditions_info_from_str() {
+        let (_, _, info) = get_test_data();
+
+        let parsed = "SP2JXKMSH007NPYAQHKJPQMAQYAD90NQGTVJVQ02B.my-contract::my-asset"
+            .parse::<AssetInfo>()
+            .unwrap();
+        assert_eq!(parsed, info);
+
+        let missing = "SP2JXKMSH007NPYAQHKJPQMAQYAD90NQGTVJVQ02B.my-contract".parse::<AssetInfo>();
+        assert!(matches!(missing, Err(Error::BadAssetFormat(_))));
+
+        let no_contract =
+            "SP2JXKMSH007NPYAQHKJPQMAQYAD90NQGTVJVQ02B::my-asset".parse::<AssetInfo>();
+        assert!(matches!(no_contract, Err(Error::BadAssetFormat(_))));
+
+        let bad_address = "not-an-address.my-contract::my-asset".parse::<AssetInfo>();
+        assert!(matches!(bad_address, Err(Error::Clarity(_))));
+    }
+
     fn get_test_data() -> (String, String, AssetInfo) {
         let addr = "SP2JXKMSH007NPYAQHKJPQMAQYAD90NQGTVJVQ02B";
         let name = "test";
@@ -502,4 +1163,47 @@ mod tests {
 
         (addr.to_string(), name.to_string(), info)
     }
+
+    #[test]
+    fn test_transaction_conditions_macro_from_vec() {
+        let (addr, _, info) = get_test_data();
+
+        let literal = post_condition!(
+            (
+                STXCondition,
+                clarity!(PrincipalStandard, addr),
+                1_000_000,
+                ConditionCode::GTE
+            ),
+            (
+                FungibleCondition,
+                clarity!(PrincipalStandard, addr),
+                500,
+                ConditionCode::LTE,
+                info.clone()
+            )
+        );
+
+        let mut conditions: Vec<Box<dyn Condition>> = vec![post_condition!(
+            STXCondition,
+            clarity!(PrincipalStandard, addr),
+            1_000_000,
+            ConditionCode::GTE
+        )];
+
+        if literal.len() > 1 {
+            conditions.push(post_condition!(
+                FungibleCondition,
+                clarity!(PrincipalStandard, addr),
+                500,
+                ConditionCode::LTE,
+                info
+            ));
+        }
+
+        let dynamic = post_condition!(from conditions);
+        assert_eq!(dynamic.len(), 2);
+        assert_eq!(dynamic.encode().unwrap(), literal.encode().unwrap());
+        assert!(post_condition!(from Vec::new()).is_empty());
+    }
 }
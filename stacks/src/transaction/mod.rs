@@ -8,6 +8,7 @@
 
 use crate::clarity;
 use crate::crypto;
+pub use crate::crypto::c32::Address;
 pub use crate::transaction::auth::Auth;
 pub use crate::transaction::auth::Modification;
 pub use crate::transaction::auth::SpendingCondition;
@@ -15,8 +16,11 @@ pub use crate::transaction::auth::SpendingConditionMultiSig;
 pub use crate::transaction::auth::SpendingConditionStandard;
 pub use crate::transaction::base::AnchorMode;
 pub use crate::transaction::base::Transaction;
+pub use crate::transaction::base::DEFAULT_MAX_FEE;
+pub use crate::transaction::builder::NamedArguments;
 pub use crate::transaction::builder::STXContractCall;
 pub use crate::transaction::builder::STXTokenTransfer;
+pub use crate::transaction::condition::decode_condition_type;
 pub use crate::transaction::condition::AssetInfo;
 pub use crate::transaction::condition::Condition;
 pub use crate::transaction::condition::ConditionCode;
@@ -29,10 +33,12 @@ pub use crate::transaction::network::ChainID;
 pub use crate::transaction::network::Network;
 pub use crate::transaction::network::StacksMainnet;
 pub use crate::transaction::network::StacksMocknet;
+pub use crate::transaction::network::StacksNetwork;
 pub use crate::transaction::network::StacksTestnet;
 pub use crate::transaction::network::TransactionVersion;
 pub use crate::transaction::payload::ContractCallPayload;
 pub use crate::transaction::payload::Payload;
+pub use crate::transaction::payload::SmartContractPayload;
 pub use crate::transaction::payload::TokenTransferPayload;
 pub use crate::transaction::signer::TransactionSigner;
 
@@ -60,6 +66,16 @@ pub enum Error {
     BadSignatureCount(u8, u8),
     #[error("Attempted to modify a spending condition with an incompatible action")]
     BadSpendingConditionModification,
+    #[error("Fee exceeds the ceiling, received {0} - max. {1}")]
+    FeeTooHigh(u64, u64),
+    #[error("Deprecated anchor mode, microblocks are no longer mined - received: {0:?}")]
+    DeprecatedAnchorMode(AnchorMode),
+    #[error("Post-condition mode `Allow` with {0} post-conditions attached, use `Deny` instead")]
+    AllowModeWithConditions(usize),
+    #[error("Invalid asset identifier, expected `address.contract::asset` - got: {0}")]
+    BadAssetFormat(String),
+    #[error("Total cost overflows, amount {0} + fee {1}")]
+    CostOverflow(u64, u64),
     /// `crypto::hex` crate errors.
     #[error(transparent)]
     Hex(#[from] crypto::hex::Error),
@@ -18,16 +18,51 @@ pub const HIRO_MOCKNET_DEFAULT: &str = "http://localhost:3999";
 
 #[repr(u8)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum TransactionVersion {
     Mainnet = 0x00,
     Testnet = 0x80,
 }
 
-#[repr(u32)]
+/// The testnet chain ID.
+pub(crate) const CHAIN_ID_TESTNET: u32 = 0x8000_0000;
+/// The mainnet chain ID.
+pub(crate) const CHAIN_ID_MAINNET: u32 = 0x0000_0001;
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum ChainID {
-    Testnet = 0x8000_0000,
-    Mainnet = 0x0000_0001,
+    Testnet,
+    Mainnet,
+    /// A custom chain ID, e.g. of a subnet.
+    Custom(u32),
+}
+
+impl ChainID {
+    /// Returns the numeric chain ID.
+    pub fn value(self) -> u32 {
+        match self {
+            Self::Testnet => CHAIN_ID_TESTNET,
+            Self::Mainnet => CHAIN_ID_MAINNET,
+            Self::Custom(id) => id,
+        }
+    }
+}
+
+impl From<u32> for ChainID {
+    fn from(id: u32) -> Self {
+        match id {
+            CHAIN_ID_TESTNET => Self::Testnet,
+            CHAIN_ID_MAINNET => Self::Mainnet,
+            id => Self::Custom(id),
+        }
+    }
+}
+
+impl From<ChainID> for u32 {
+    fn from(id: ChainID) -> Self {
+        id.value()
+    }
 }
 
 pub trait Network: DynClone {
@@ -62,6 +97,86 @@ impl_network_type!(
     HIRO_MOCKNET_DEFAULT
 );
 
+/// A network selected at runtime.
+///
+/// With the `serde` feature, the network can be persisted, e.g. as `"mainnet"` or
+/// `{"mocknet": "http://localhost:3999"}` in JSON.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum StacksNetwork {
+    /// The mainnet network, using the default Hiro API.
+    Mainnet,
+    /// The testnet network, using the default Hiro API.
+    Testnet,
+    /// A mocknet network, using a custom API URL.
+    Mocknet(String),
+    /// A custom chain (e.g. a subnet), using a custom chain ID & API URL.
+    Custom {
+        /// The chain ID used when signing transactions.
+        chain_id: u32,
+        /// The transaction version.
+        version: TransactionVersion,
+        /// The API base URL.
+        url: String,
+    },
+}
+
+impl StacksNetwork {
+    /// Creates a new mainnet `StacksNetwork`.
+    pub fn mainnet() -> Self {
+        Self::Mainnet
+    }
+
+    /// Creates a new testnet `StacksNetwork`.
+    pub fn testnet() -> Self {
+        Self::Testnet
+    }
+
+    /// Creates a new mocknet `StacksNetwork` with the default local API URL.
+    pub fn mocknet() -> Self {
+        Self::Mocknet(HIRO_MOCKNET_DEFAULT.into())
+    }
+
+    /// Creates a new custom `StacksNetwork`, e.g. for a subnet.
+    pub fn custom<T>(chain_id: u32, version: TransactionVersion, url: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self::Custom {
+            chain_id,
+            version,
+            url: url.into(),
+        }
+    }
+}
+
+impl Network for StacksNetwork {
+    fn version(&self) -> TransactionVersion {
+        match self {
+            Self::Mainnet => TransactionVersion::Mainnet,
+            Self::Testnet | Self::Mocknet(_) => TransactionVersion::Testnet,
+            Self::Custom { version, .. } => *version,
+        }
+    }
+
+    fn chain_id(&self) -> ChainID {
+        match self {
+            Self::Mainnet => ChainID::Mainnet,
+            Self::Testnet | Self::Mocknet(_) => ChainID::Testnet,
+            Self::Custom { chain_id, .. } => ChainID::from(*chain_id),
+        }
+    }
+
+    fn base_url(&self) -> String {
+        match self {
+            Self::Mainnet => HIRO_MAINNET_DEFAULT.into(),
+            Self::Testnet => HIRO_TESTNET_DEFAULT.into(),
+            Self::Mocknet(url) | Self::Custom { url, .. } => url.clone(),
+        }
+    }
+}
+
 macro_rules! impl_network_type {
     ($name:ident, $version:expr, $chain_id:expr, $api:expr) => {
         #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -104,3 +219,96 @@ macro_rules! impl_network_type {
 }
 
 pub(crate) use impl_network_type;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transaction_network_runtime_selection() {
+        let networks: Vec<Box<dyn Network>> = vec![
+            Box::new(StacksNetwork::mainnet()),
+            Box::new(StacksNetwork::testnet()),
+            Box::new(StacksNetwork::mocknet()),
+            Box::new(StacksNetwork::Mocknet("http://127.0.0.1:20443".into())),
+        ];
+
+        let expected = [
+            (
+                TransactionVersion::Mainnet,
+                ChainID::Mainnet,
+                HIRO_MAINNET_DEFAULT,
+            ),
+            (
+                TransactionVersion::Testnet,
+                ChainID::Testnet,
+                HIRO_TESTNET_DEFAULT,
+            ),
+            (
+                TransactionVersion::Testnet,
+                ChainID::Testnet,
+                HIRO_MOCKNET_DEFAULT,
+            ),
+            (
+                TransactionVersion::Testnet,
+                ChainID::Testnet,
+                "http://127.0.0.1:20443",
+            ),
+        ];
+
+        for (network, (version, chain_id, url)) in networks.iter().zip(expected) {
+            assert_eq!(network.version(), version);
+            assert_eq!(network.chain_id(), chain_id);
+            assert_eq!(network.base_url(), url);
+        }
+
+        let cloned = networks[0].clone();
+        assert_eq!(cloned.chain_id(), StacksMainnet::new().chain_id());
+    }
+
+    #[test]
+    fn test_transaction_network_custom_chain_id() {
+        let subnet = StacksNetwork::custom(
+            0x5533_0000,
+            TransactionVersion::Testnet,
+            "http://127.0.0.1:30443",
+        );
+
+        assert_eq!(subnet.version(), TransactionVersion::Testnet);
+        assert_eq!(subnet.chain_id(), ChainID::Custom(0x5533_0000));
+        assert_eq!(subnet.chain_id().value(), 0x5533_0000);
+        assert_eq!(subnet.base_url(), "http://127.0.0.1:30443");
+
+        assert_eq!(ChainID::from(0x8000_0000), ChainID::Testnet);
+        assert_eq!(ChainID::from(0x0000_0001), ChainID::Mainnet);
+        assert_eq!(u32::from(ChainID::Mainnet), 1);
+        assert_eq!(ChainID::Testnet.value(), 0x8000_0000);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_transaction_network_serde() {
+        let networks = [
+            (StacksNetwork::mainnet(), r#""mainnet""#),
+            (StacksNetwork::testnet(), r#""testnet""#),
+            (
+                StacksNetwork::Mocknet("http://127.0.0.1:20443".into()),
+                r#"{"mocknet":"http://127.0.0.1:20443"}"#,
+            ),
+            (
+                StacksNetwork::custom(0x5533_0001, TransactionVersion::Testnet, "http://subnet"),
+                r#"{"custom":{"chain_id":1429405697,"version":"testnet","url":"http://subnet"}}"#,
+            ),
+        ];
+
+        for (network, json) in networks {
+            assert_eq!(serde_json::to_string(&network).unwrap(), json);
+
+            let decoded = serde_json::from_str::<StacksNetwork>(json).unwrap();
+            assert_eq!(decoded.base_url(), network.base_url());
+            assert_eq!(decoded, network);
+        }
+
+        assert!(serde_json::from_str::<StacksNetwork>(r#""devnet""#).is_err());
+    }
+}
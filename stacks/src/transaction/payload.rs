@@ -7,26 +7,61 @@
 // Usage of this file is permitted solely under a sanctioned license.
 
 use std::fmt::Debug;
+use std::str::FromStr;
 
 use dyn_clone::clone_trait_object;
 use dyn_clone::DynClone;
 
 use crate::clarity;
 use crate::clarity::decode_clarity_type;
+use crate::clarity::impls::checked_read;
+use crate::clarity::impls::checked_slice;
 use crate::clarity::Clarity;
 use crate::clarity::Codec;
 use crate::clarity::FnArguments;
 use crate::clarity::LengthPrefixedStr;
 use crate::crypto::c32::Address;
+#[cfg(feature = "serde")]
+use crate::crypto::c32_address;
 use crate::crypto::Hash160;
 
 /// The token-transfer payload type.
 pub(crate) const PAYLOAD_TYPE_TOKEN_TRANSFER: u8 = 0x00;
+/// The smart-contract payload type.
+pub(crate) const PAYLOAD_TYPE_SMART_CONTRACT: u8 = 0x01;
 /// The contract-call payload type.
 pub(crate) const PAYLOAD_TYPE_CONTRACT_CALL: u8 = 0x02;
 
-/// Marker trait for transaction payloads.
-pub trait Payload: Codec + DynClone + Debug {}
+/// The maximum length of a contract or function name, in bytes.
+pub(crate) const CLARITY_NAME_MAX_LEN: usize = 128;
+
+/// Returns `true` if `name` is a valid Clarity contract or function name.
+///
+/// Names must match `[a-zA-Z]([a-zA-Z0-9]|[-_!?+<>=/*])*` & fit the
+/// length-prefix of at most 128 bytes.
+pub(crate) fn is_clarity_name(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    name.len() <= CLARITY_NAME_MAX_LEN
+        && chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || "-_!?+<>=/*".contains(c))
+}
+
+/// Trait for transaction payloads.
+pub trait Payload: Codec + DynClone + Debug {
+    /// Returns a JSON description of the payload.
+    ///
+    /// Defaults to the hex-encoded bytes, so implementors outside this crate
+    /// keep compiling when the `serde` feature is enabled.
+    #[cfg(feature = "serde")]
+    fn to_json(&self) -> serde_json::Value {
+        let hex = self
+            .encode()
+            .ok()
+            .map(|bytes| crate::crypto::bytes_to_hex(&bytes));
+        serde_json::json!({ "hex": hex })
+    }
+}
 clone_trait_object!(Payload);
 
 impl Codec for Box<dyn Payload> {
@@ -34,20 +69,30 @@ impl Codec for Box<dyn Payload> {
         self.as_ref().encode()
     }
 
+    fn encode_into(&self, out: &mut Vec<u8>) -> Result<(), clarity::Error> {
+        self.as_ref().encode_into(out)
+    }
+
     fn decode(bytes: &[u8]) -> Result<Self, clarity::Error>
     where
         Self: Sized,
     {
-        match bytes[0] {
+        let tag = checked_read(bytes, 0, 1)?[0];
+
+        match tag {
             PAYLOAD_TYPE_TOKEN_TRANSFER => {
                 let payload = TokenTransferPayload::decode(bytes)?;
                 Ok(Box::new(payload))
             }
+            PAYLOAD_TYPE_SMART_CONTRACT => {
+                let payload = SmartContractPayload::decode(bytes)?;
+                Ok(Box::new(payload))
+            }
             PAYLOAD_TYPE_CONTRACT_CALL => {
                 let payload = ContractCallPayload::decode(bytes)?;
                 Ok(Box::new(payload))
             }
-            _ => Err(clarity::Error::UnexpectedType(bytes[0])),
+            _ => Err(clarity::Error::UnexpectedType(tag)),
         }
     }
 }
@@ -76,6 +121,11 @@ impl TokenTransferPayload {
             memo: memo.into(),
         }
     }
+
+    /// Returns the memo with the trailing `NUL` padding stripped.
+    pub fn memo_str(&self) -> &str {
+        self.memo.trim_end_matches('\0')
+    }
 }
 
 impl Codec for TokenTransferPayload {
@@ -102,8 +152,10 @@ impl Codec for TokenTransferPayload {
     where
         Self: Sized,
     {
-        if bytes[0] != PAYLOAD_TYPE_TOKEN_TRANSFER {
-            return Err(clarity::Error::UnexpectedType(bytes[0]));
+        let tag = checked_read(bytes, 0, 1)?[0];
+
+        if tag != PAYLOAD_TYPE_TOKEN_TRANSFER {
+            return Err(clarity::Error::UnexpectedType(tag));
         }
 
         let mut offset = 1;
@@ -113,12 +165,12 @@ impl Codec for TokenTransferPayload {
 
         offset += addr_len;
 
-        let amount_bytes = &bytes[offset..offset + 8];
+        let amount_bytes = checked_read(bytes, offset, 8)?;
         let amount = u64::from_be_bytes(amount_bytes.try_into()?);
 
         offset += 8;
 
-        let memo_bytes = &bytes[offset..offset + 34];
+        let memo_bytes = checked_read(bytes, offset, 34)?;
         let memo = String::from_utf8(memo_bytes.to_vec())?;
 
         Ok(Self {
@@ -129,7 +181,17 @@ impl Codec for TokenTransferPayload {
     }
 }
 
-impl Payload for TokenTransferPayload {}
+impl Payload for TokenTransferPayload {
+    #[cfg(feature = "serde")]
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "token_transfer",
+            "recipient": self.address.to_string(),
+            "amount": self.amount,
+            "memo": self.memo_str(),
+        })
+    }
+}
 
 /// The payload type for a contract call.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -145,57 +207,94 @@ pub struct ContractCallPayload {
 }
 
 impl ContractCallPayload {
-    /// Creates a new `ContractCallPayload`
-    pub fn new<T, K>(address: Address, contract: T, name: K, args: FnArguments) -> Self
+    /// Creates a new `ContractCallPayload`.
+    ///
+    /// Fails if the contract or function name is not a valid Clarity name.
+    pub fn new<T, K>(
+        address: Address,
+        contract: T,
+        name: K,
+        args: FnArguments,
+    ) -> Result<Self, clarity::Error>
     where
         T: Into<LengthPrefixedStr>,
         K: Into<LengthPrefixedStr>,
     {
-        Self {
+        let contract = contract.into();
+        let name = name.into();
+
+        if !is_clarity_name(&contract) {
+            return Err(clarity::Error::BadContractName(contract.into_value()));
+        }
+
+        if !is_clarity_name(&name) {
+            return Err(clarity::Error::BadFunctionName(name.into_value()));
+        }
+
+        Ok(Self {
             address,
-            contract: contract.into(),
-            name: name.into(),
+            contract,
+            name,
             args,
-        }
+        })
+    }
+
+    /// Creates a new `ContractCallPayload` from a c32 encoded address.
+    pub fn from_principal<T, K>(
+        address: &str,
+        contract: T,
+        name: K,
+        args: FnArguments,
+    ) -> Result<Self, clarity::Error>
+    where
+        T: Into<LengthPrefixedStr>,
+        K: Into<LengthPrefixedStr>,
+    {
+        let address = Address::from_str(address)?;
+        Self::new(address, contract, name, args)
     }
 }
 
 impl Codec for ContractCallPayload {
     fn encode(&self) -> Result<Vec<u8>, clarity::Error> {
-        let mut buff = vec![PAYLOAD_TYPE_CONTRACT_CALL];
-        buff.extend_from_slice(&[self.address.version]);
-        buff.extend_from_slice(self.address.hash.as_bytes());
-        buff.extend_from_slice(&self.contract.encode()?);
-        buff.extend_from_slice(&self.name.encode()?);
-        buff.extend_from_slice(&self.args.encode()?);
+        let mut buff = vec![];
+        self.encode_into(&mut buff)?;
         Ok(buff)
     }
 
+    fn encode_into(&self, out: &mut Vec<u8>) -> Result<(), clarity::Error> {
+        out.push(PAYLOAD_TYPE_CONTRACT_CALL);
+        out.push(self.address.version);
+        out.extend_from_slice(self.address.hash.as_bytes());
+        self.contract.encode_into(out)?;
+        self.name.encode_into(out)?;
+        self.args.encode_into(out)
+    }
+
     fn decode(bytes: &[u8]) -> Result<Self, clarity::Error>
     where
         Self: Sized,
     {
-        if bytes[0] != PAYLOAD_TYPE_CONTRACT_CALL {
-            return Err(clarity::Error::UnexpectedType(bytes[0]));
+        let tag = checked_read(bytes, 0, 1)?[0];
+
+        if tag != PAYLOAD_TYPE_CONTRACT_CALL {
+            return Err(clarity::Error::UnexpectedType(tag));
         }
 
         let mut offset = 1;
 
-        let version = bytes[offset];
-        offset += 1;
+        let addr = checked_read(bytes, offset, 21)?;
+        let address = Address::new(Hash160::new(&addr[1..]), addr[0]);
 
-        let hash = Hash160::new(&bytes[offset..offset + 20]);
-        let address = Address::new(hash, version);
-
-        offset += 20;
+        offset += 21;
 
         let contract = LengthPrefixedStr::decode(&bytes[offset..])?;
-        let contract_len = contract.len()?;
+        offset += contract.len()?;
 
-        let name = LengthPrefixedStr::decode(&bytes[offset + contract_len..])?;
-        let name_len = name.len()?;
+        let name = LengthPrefixedStr::decode(&bytes[offset..])?;
+        offset += name.len()?;
 
-        let args = FnArguments::decode(&bytes[offset + contract_len + name_len..])?;
+        let args = FnArguments::decode(&bytes[offset..])?;
 
         Ok(Self {
             address,
@@ -206,12 +305,98 @@ impl Codec for ContractCallPayload {
     }
 }
 
-impl Payload for ContractCallPayload {}
+impl Payload for ContractCallPayload {
+    #[cfg(feature = "serde")]
+    fn to_json(&self) -> serde_json::Value {
+        let address = c32_address(self.address.hash.as_bytes(), self.address.version).ok();
+        let args = self
+            .args
+            .value()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+
+        serde_json::json!({
+            "type": "contract_call",
+            "address": address,
+            "contract": self.contract.value(),
+            "function": self.name.value(),
+            "args": args,
+        })
+    }
+}
+
+/// The payload type for a contract deploy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SmartContractPayload {
+    /// The name of the contract to deploy.
+    pub name: LengthPrefixedStr,
+    /// The clarity source of the contract.
+    pub source: String,
+}
+
+impl SmartContractPayload {
+    /// Creates a new `SmartContractPayload`
+    pub fn new<T, S>(name: T, source: S) -> Self
+    where
+        T: Into<LengthPrefixedStr>,
+        S: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            source: source.into(),
+        }
+    }
+}
+
+impl Codec for SmartContractPayload {
+    fn encode(&self) -> Result<Vec<u8>, clarity::Error> {
+        let len = u32::try_from(self.source.len())?;
+
+        let mut buff = vec![PAYLOAD_TYPE_SMART_CONTRACT];
+        buff.extend_from_slice(&self.name.encode()?);
+        buff.extend_from_slice(&len.to_be_bytes());
+        buff.extend_from_slice(self.source.as_bytes());
+        Ok(buff)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, clarity::Error>
+    where
+        Self: Sized,
+    {
+        let tag = checked_read(bytes, 0, 1)?[0];
+
+        if tag != PAYLOAD_TYPE_SMART_CONTRACT {
+            return Err(clarity::Error::UnexpectedType(tag));
+        }
+
+        let mut offset = 1;
+
+        let name = LengthPrefixedStr::decode(&bytes[offset..])?;
+        offset += name.len()?;
+
+        let len = u32::from_be_bytes(checked_read(bytes, offset, 4)?.try_into()?) as usize;
+        offset += 4;
+
+        let source = String::from_utf8(checked_slice(bytes, offset, len)?.to_vec())?;
+
+        Ok(Self { name, source })
+    }
+}
+
+impl Payload for SmartContractPayload {
+    #[cfg(feature = "serde")]
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "smart_contract",
+            "name": self.name.value(),
+            "source": self.source,
+        })
+    }
+}
 
 #[cfg(test)]
 mod tests {
-    use std::str::FromStr;
-
     use super::*;
     use crate::clarity::False;
     use crate::clarity::Int;
@@ -221,6 +406,20 @@ mod tests {
     use crate::clarity::UInt;
     use crate::crypto::hex::bytes_to_hex;
 
+    #[test]
+    fn test_transaction_payload_token_transfer_memo() {
+        let payload = TokenTransferPayload::new(get_test_standard_cv(), 100_000, "test memo");
+
+        let decoded = TokenTransferPayload::decode(&payload.encode().unwrap()).unwrap();
+        assert_eq!(decoded.memo.len(), 34);
+        assert_eq!(decoded.memo_str(), "test memo");
+        assert_eq!(decoded.memo_str(), payload.memo_str());
+
+        let empty = TokenTransferPayload::new(get_test_standard_cv(), 0, "");
+        let decoded = TokenTransferPayload::decode(&empty.encode().unwrap()).unwrap();
+        assert_eq!(decoded.memo_str(), "");
+    }
+
     #[test]
     fn test_transaction_payload_token_transfer_encode() {
         let std = get_test_standard_cv();
@@ -265,7 +464,7 @@ mod tests {
         let (address, contract, fn_name) = get_test_contract_fixtures();
 
         let fn_args = clarity!(FnArguments, UInt::new(100), Int::new(-100));
-        let payload = ContractCallPayload::new(address, contract, fn_name, fn_args);
+        let payload = ContractCallPayload::new(address, contract, fn_name, fn_args).unwrap();
 
         let encoded = payload.encode().unwrap();
         let decoded = ContractCallPayload::decode(&encoded).unwrap();
@@ -276,12 +475,48 @@ mod tests {
         assert_eq!(hex, expected);
     }
 
+    #[test]
+    fn test_transaction_payload_smart_contract_encode() {
+        let payload = SmartContractPayload::new("hello", "(define-read-only (hello) u1)");
+
+        let hex = bytes_to_hex(payload.encode().unwrap());
+        let expected =
+            "010568656c6c6f0000001d28646566696e652d726561642d6f6e6c79202868656c6c6f2920753129";
+        assert_eq!(hex, expected);
+
+        let decoded = SmartContractPayload::decode(&payload.encode().unwrap()).unwrap();
+        assert_eq!(decoded, payload);
+
+        let boxed: Box<dyn Payload> = Box::new(payload.clone());
+        let decoded = Box::<dyn Payload>::decode(&boxed.encode().unwrap()).unwrap();
+        assert_eq!(decoded.encode().unwrap(), payload.encode().unwrap());
+    }
+
+    #[test]
+    fn test_transaction_payload_contract_call_from_principal() {
+        let fn_args = clarity!(FnArguments, UInt::new(100), Int::new(-100));
+        let payload = ContractCallPayload::from_principal(
+            "STB44HYPYAT2BB2QE513NSP81HTMYWBJP02HPGK6",
+            "my-contract",
+            "my-function",
+            fn_args,
+        )
+        .unwrap();
+
+        let hex = bytes_to_hex(payload.encode().unwrap());
+        let expected = "021a164247d6f2b425ac5771423ae6c80c754f7172b00b6d792d636f6e74726163740b6d792d66756e6374696f6e00000002010000000000000000000000000000006400ffffffffffffffffffffffffffffff9c";
+        assert_eq!(hex, expected);
+
+        let bad = ContractCallPayload::from_principal("bad", "a", "b", clarity!(FnArguments));
+        assert!(bad.is_err());
+    }
+
     #[test]
     fn test_transaction_payload_contract_call_encode_empty() {
         let (address, contract, fn_name) = get_test_contract_fixtures();
         let fn_args = clarity!(FnArguments);
 
-        let payload = ContractCallPayload::new(address, contract, fn_name, fn_args);
+        let payload = ContractCallPayload::new(address, contract, fn_name, fn_args).unwrap();
 
         let encoded = payload.encode().unwrap();
         let decoded = ContractCallPayload::decode(&encoded).unwrap();
@@ -313,7 +548,7 @@ mod tests {
             Int::new(-100000)
         );
 
-        let payload = ContractCallPayload::new(address, contract, fn_name, fn_args);
+        let payload = ContractCallPayload::new(address, contract, fn_name, fn_args).unwrap();
 
         let encoded = payload.encode().unwrap();
         let decoded = ContractCallPayload::decode(&encoded).unwrap();
@@ -324,6 +559,58 @@ mod tests {
         assert_eq!(hex, expected);
     }
 
+    #[test]
+    fn test_transaction_payload_decode_truncated() {
+        let (address, contract, fn_name) = get_test_contract_fixtures();
+        let fn_args = clarity!(FnArguments, UInt::new(100), Int::new(-100));
+
+        let transfers = [
+            TokenTransferPayload::new(get_test_standard_cv(), 100_000, "memo"),
+            TokenTransferPayload::new(get_test_contract_cv(), 100_000, "memo"),
+        ];
+        let call = ContractCallPayload::new(address, contract, fn_name, fn_args).unwrap();
+        let deploy = SmartContractPayload::new("hello", "(define-read-only (hello) u1)");
+
+        let encoded = [
+            transfers[0].encode().unwrap(),
+            transfers[1].encode().unwrap(),
+            call.encode().unwrap(),
+            deploy.encode().unwrap(),
+        ];
+
+        for bytes in &encoded {
+            for len in 0..bytes.len() {
+                let truncated = &bytes[..len];
+                assert!(Box::<dyn Payload>::decode(truncated).is_err());
+            }
+            assert!(Box::<dyn Payload>::decode(bytes).is_ok());
+        }
+
+        let err = Box::<dyn Payload>::decode(&[]).unwrap_err();
+        assert!(matches!(
+            err,
+            clarity::Error::UnexpectedEnd {
+                expected: 1,
+                available: 0
+            }
+        ));
+
+        let err = ContractCallPayload::decode(&encoded[2][..10]).unwrap_err();
+        assert!(matches!(
+            err,
+            clarity::Error::UnexpectedEnd {
+                expected: 21,
+                available: 9
+            }
+        ));
+
+        let err = TokenTransferPayload::decode(&encoded[0][..30]).unwrap_err();
+        assert!(matches!(
+            err,
+            clarity::Error::UnexpectedEnd { expected: 8, .. }
+        ));
+    }
+
     fn get_test_standard_cv() -> PrincipalStandard {
         clarity!(
             PrincipalStandard,
@@ -339,10 +626,78 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_transaction_payload_contract_call_names() {
+        let (address, _, _) = get_test_contract_fixtures();
+        let call = |contract: &str, name: &str| {
+            ContractCallPayload::new(address.clone(), contract, name, clarity!(FnArguments))
+        };
+
+        for name in [
+            "a",
+            "my-function",
+            "get-balance?",
+            "transfer!",
+            "a_b+c<d>e=f/g*h",
+            "A1",
+        ] {
+            assert!(call("my-contract", name).is_ok(), "{name}");
+        }
+
+        for name in ["my function", "1function", "-function", "", "fn.name"] {
+            assert!(matches!(
+                call("my-contract", name),
+                Err(clarity::Error::BadFunctionName(ref n)) if *n == name
+            ));
+        }
+
+        assert!(matches!(
+            call("my contract", "my-function"),
+            Err(clarity::Error::BadContractName(ref n)) if *n == "my contract"
+        ));
+        assert!(matches!(
+            call("1contract", "my-function"),
+            Err(clarity::Error::BadContractName(ref n)) if *n == "1contract"
+        ));
+
+        let long = "a".repeat(CLARITY_NAME_MAX_LEN + 1);
+        assert!(call("my-contract", &"a".repeat(CLARITY_NAME_MAX_LEN)).is_ok());
+        assert!(matches!(
+            call("my-contract", &long),
+            Err(clarity::Error::BadFunctionName(ref n)) if *n == long
+        ));
+        assert!(matches!(
+            call(&long, "my-function"),
+            Err(clarity::Error::BadContractName(ref n)) if *n == long
+        ));
+    }
+
     fn get_test_contract_fixtures() -> (Address, String, String) {
         let addr = Address::from_str("STB44HYPYAT2BB2QE513NSP81HTMYWBJP02HPGK6").unwrap();
         let contract_name = String::from("my-contract");
         let fn_name = String::from("my-function");
         (addr, contract_name.to_string(), fn_name.to_string())
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_transaction_payload_default_to_json() {
+        #[derive(Debug, Clone)]
+        struct RawPayload(Vec<u8>);
+
+        impl Codec for RawPayload {
+            fn encode(&self) -> Result<Vec<u8>, clarity::Error> {
+                Ok(self.0.clone())
+            }
+
+            fn decode(bytes: &[u8]) -> Result<Self, clarity::Error> {
+                Ok(Self(bytes.to_vec()))
+            }
+        }
+
+        impl Payload for RawPayload {}
+
+        let payload: Box<dyn Payload> = Box::new(RawPayload(vec![0xde, 0xad]));
+        assert_eq!(payload.to_json(), serde_json::json!({ "hex": "dead" }));
+    }
 }
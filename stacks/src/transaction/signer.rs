@@ -82,13 +82,24 @@ impl TransactionSigner {
     }
 
     /// Signs the sponsor of the transaction.
+    ///
+    /// Once the sponsor has all required signatures, the condition is verified against
+    /// the sponsor's signer hash, returning `Error::BadSigner` on a key/hash-mode mismatch.
     pub fn sign_sponsor(&mut self, key: SecretKey) -> Result<(), Error> {
         let sponsor = self.tx.auth.sponsor()?;
 
         if self.verify_oversign && sponsor.signatures() >= sponsor.req_signatures() {
             Err(Error::SponsorOversign)
         } else {
-            let next = self.tx.sign_next_sponsor(self.hash, key)?;
+            let mut tx = self.tx.clone();
+            let next = tx.sign_next_sponsor(self.hash, key)?;
+
+            let sponsor = tx.auth.sponsor()?;
+            if sponsor.signatures() >= sponsor.req_signatures() {
+                tx.auth.verify(tx.initial_hash()?)?;
+            }
+
+            self.tx = tx;
             self.origin_signed = true;
             self.hash = next;
             Ok(())
@@ -8,16 +8,50 @@
 
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
+use std::format as f;
+use std::ops::Range;
 use std::str::FromStr;
 
+#[cfg(feature = "keystore")]
+use aes_gcm::aead::Aead;
+#[cfg(feature = "keystore")]
+use aes_gcm::Aes256Gcm;
+#[cfg(feature = "keystore")]
+use aes_gcm::KeyInit;
+#[cfg(feature = "keystore")]
+use aes_gcm::Nonce;
+pub use bip32::ChildNumber;
 use bip32::DerivationPath;
 use bip32::XPrv;
+pub use bip39::Mnemonic;
+use ring::digest::digest;
+use ring::digest::SHA512;
+use ring::rand::SecureRandom;
+use ring::rand::SystemRandom;
 use secp256k1::PublicKey;
 use secp256k1::SecretKey;
+#[cfg(feature = "keystore")]
+use serde::Deserialize;
+#[cfg(feature = "keystore")]
+use serde::Serialize;
+use zeroize::Zeroizing;
 
+#[cfg(feature = "transaction")]
+use crate::clarity::PrincipalStandard;
+use crate::crypto::b58;
+use crate::crypto::base58check_decode;
+#[cfg(feature = "keystore")]
+use crate::crypto::bytes_to_hex;
 use crate::crypto::c32;
 use crate::crypto::c32::hash_p2pkh;
+#[cfg(feature = "transaction")]
+use crate::crypto::c32::Mode;
 use crate::crypto::c32::Version;
+use crate::crypto::hex;
+use crate::crypto::hex_to_bytes;
+use crate::crypto::Hash160;
+#[cfg(feature = "transaction")]
+use crate::transaction::TransactionVersion;
 
 /// Error variants for the wallet-sdk.
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
@@ -34,10 +68,81 @@ pub enum Error {
     /// `secp256k1` crate errors.
     #[error(transparent)]
     Secp256k1(#[from] secp256k1::Error),
+    /// The mnemonic phrase failed validation (word count, word list or
+    /// checksum).
+    #[error("Invalid mnemonic phrase: {0}")]
+    InvalidMnemonic(bip39::Error),
+    /// The keystore could not be decrypted with the provided password.
+    #[error("Failed to decrypt keystore, bad password")]
+    BadPassword,
+    /// The keystore is malformed or uses an unsupported scheme.
+    #[error("Bad keystore: {0}")]
+    BadKeystore(String),
+    /// `crypto::b58` crate errors.
+    #[error(transparent)]
+    Base58(#[from] b58::Error),
+    /// `crypto::hex` crate errors.
+    #[error(transparent)]
+    Hex(#[from] hex::Error),
+    /// The private key has an unexpected length, version or suffix.
+    #[error("Bad private key: {0}")]
+    BadPrivateKey(String),
+    /// The seed has an unexpected length.
+    #[error("Bad seed length - received {0} bytes, expected one of '[16, 32, 64]'")]
+    BadSeed(usize),
+    /// The system random number generator failed.
+    #[error("Failed to generate entropy")]
+    BadEntropy,
 }
 
 /// The derivation path for Stacks accounts.
 pub(crate) const STX_DERIVATION_PATH: &str = "m/44'/5757'/0'/0";
+/// The derivation path for identity data keys, as used by Stacks Connect & Gaia.
+pub(crate) const DATA_DERIVATION_PATH: &str = "m/888'/0'";
+
+/// The version of the keystore format, sealing the hex encoded seed.
+#[cfg(feature = "keystore")]
+pub(crate) const KEYSTORE_VERSION: u8 = 2;
+/// The key derivation function used by the keystore.
+#[cfg(feature = "keystore")]
+pub(crate) const KEYSTORE_KDF: &str = "scrypt";
+/// The cipher used by the keystore.
+#[cfg(feature = "keystore")]
+pub(crate) const KEYSTORE_CIPHER: &str = "aes-256-gcm";
+/// The default scrypt cost parameter (log2 of N).
+#[cfg(feature = "keystore")]
+pub(crate) const KEYSTORE_SCRYPT_LOG_N: u8 = 15;
+/// The default scrypt block size parameter.
+#[cfg(feature = "keystore")]
+pub(crate) const KEYSTORE_SCRYPT_R: u32 = 8;
+/// The default scrypt parallelization parameter.
+#[cfg(feature = "keystore")]
+pub(crate) const KEYSTORE_SCRYPT_P: u32 = 1;
+/// The maximum accepted scrypt cost parameter, bounding memory to 128 * r * 2^18 bytes.
+#[cfg(feature = "keystore")]
+pub(crate) const KEYSTORE_SCRYPT_MAX_LOG_N: u8 = 18;
+/// The maximum accepted scrypt block size parameter.
+#[cfg(feature = "keystore")]
+pub(crate) const KEYSTORE_SCRYPT_MAX_R: u32 = 8;
+/// The maximum accepted scrypt parallelization parameter.
+#[cfg(feature = "keystore")]
+pub(crate) const KEYSTORE_SCRYPT_MAX_P: u32 = 4;
+/// The length of the derived encryption key.
+#[cfg(feature = "keystore")]
+pub(crate) const KEYSTORE_KEY_LEN: usize = 32;
+/// The length of the scrypt salt.
+#[cfg(feature = "keystore")]
+pub(crate) const KEYSTORE_SALT_LEN: usize = 32;
+/// The length of the AES-GCM nonce.
+#[cfg(feature = "keystore")]
+pub(crate) const KEYSTORE_NONCE_LEN: usize = 12;
+
+/// The WIF version byte for mainnet private keys.
+pub(crate) const WIF_VERSION_MAINNET: u8 = 0x80;
+/// The WIF version byte for testnet private keys.
+pub(crate) const WIF_VERSION_TESTNET: u8 = 0xef;
+/// The suffix marking a private key as compressed.
+pub(crate) const PRIVATE_KEY_COMPRESSED: u8 = 0x01;
 
 /// A map of `StacksAccount` instances, indexed by derivation index.
 pub type StacksAccounts = HashMap<u32, StacksAccount>;
@@ -66,10 +171,17 @@ impl StacksAccount {
     /// Returns the address of the account for a given version.
     pub fn get_address(&self, version: Version) -> Result<String, Error> {
         let addr = hash_p2pkh(&self.ext.public_key().to_bytes());
-        let c32 = c32::c32_address(addr.as_bytes(), version as u8)?;
+        let c32 = c32::c32_address_typed(addr.as_bytes(), version)?;
         Ok(c32)
     }
 
+    /// Returns the P2PKH principal of the account for a given network.
+    #[cfg(feature = "transaction")]
+    pub fn principal(&self, network: TransactionVersion) -> Result<PrincipalStandard, Error> {
+        let version = Version::from_mode(Mode::P2PKH, network);
+        Ok(PrincipalStandard::new(self.get_address(version)?))
+    }
+
     /// Returns the private key of the wallet.
     pub fn private_key(&self) -> Result<SecretKey, Error> {
         Ok(SecretKey::from_slice(&self.ext.private_key().to_bytes())?)
@@ -79,6 +191,16 @@ impl StacksAccount {
     pub fn public_key(&self) -> Result<PublicKey, Error> {
         Ok(PublicKey::from_slice(&self.ext.public_key().to_bytes())?)
     }
+
+    /// Returns the BIP32 identifier of the extended key, the `Hash160` of its public key.
+    pub fn identifier(&self) -> Hash160 {
+        key_identifier(&self.ext)
+    }
+
+    /// Returns the BIP32 fingerprint of the extended key, the first 4 bytes of its identifier.
+    pub fn fingerprint(&self) -> [u8; 4] {
+        key_fingerprint(&self.ext)
+    }
 }
 
 /// A `StacksWallet`, which contains a root key and a map of derived accounts.
@@ -88,24 +210,111 @@ pub struct StacksWallet {
     ext: XPrv,
     /// The accounts derived from the root key.
     accounts: StacksAccounts,
+    /// The seed of the wallet, kept for data keys & keystores.
+    seed: Seed,
+}
+
+/// The seed of a `StacksWallet`, zeroized on drop & redacted from debug output.
+#[derive(Clone, PartialEq, Eq)]
+struct Seed(Zeroizing<Vec<u8>>);
+
+impl std::fmt::Debug for Seed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Seed(..)")
+    }
 }
 
 impl StacksWallet {
     /// Creates a new `StacksWallet`.
-    fn new(ext: XPrv, accounts: StacksAccounts) -> Self {
-        Self { ext, accounts }
+    fn new(ext: XPrv, accounts: StacksAccounts, seed: Seed) -> Self {
+        Self {
+            ext,
+            accounts,
+            seed,
+        }
+    }
+
+    /// Derives the account path from a seed, the seed is kept for data keys.
+    fn from_seed(seed: &[u8]) -> Result<Self, Error> {
+        let path = DerivationPath::from_str(STX_DERIVATION_PATH)?;
+        let ext = XPrv::derive_from_path(seed, &path)?;
+
+        let seed = Seed(Zeroizing::new(seed.to_vec()));
+        Ok(Self::new(ext, StacksAccounts::new(), seed))
     }
 
     /// Creates a new `StacksWallet` from a secret key / mnemonic phrase.
+    ///
+    /// Fails with `Error::Bip39` on an invalid phrase, unlike `from_mnemonic`.
     pub fn from_secret_key<S>(sk: S) -> Result<Self, Error>
     where
         S: Into<String>,
     {
-        let mnemonic = bip39::Mnemonic::parse(sk.into())?;
-        let seed = mnemonic.to_seed_normalized("");
-        let path = DerivationPath::from_str(STX_DERIVATION_PATH)?;
-        let ext = XPrv::derive_from_path(seed, &path)?;
-        Ok(Self::new(ext, StacksAccounts::new()))
+        let mnemonic = Mnemonic::parse(sk.into())?;
+        let seed = Zeroizing::new(mnemonic.to_seed_normalized(""));
+        Self::from_seed(seed.as_ref())
+    }
+
+    /// Creates a new `StacksWallet` from a mnemonic phrase & an optional
+    /// BIP39 passphrase.
+    ///
+    /// Accepts 12, 15, 18, 21 or 24 word phrases, an empty passphrase is
+    /// equivalent to no passphrase.
+    pub fn from_mnemonic<S, P>(phrase: S, passphrase: P) -> Result<Self, Error>
+    where
+        S: Into<String>,
+        P: AsRef<str>,
+    {
+        let mnemonic = Mnemonic::parse(phrase.into()).map_err(Error::InvalidMnemonic)?;
+        let seed = Zeroizing::new(mnemonic.to_seed_normalized(passphrase.as_ref()));
+        Self::from_seed(seed.as_ref())
+    }
+
+    /// Creates a new `StacksWallet` from a raw 16, 32 or 64 byte seed.
+    pub fn from_seed_bytes(seed: &[u8]) -> Result<Self, Error> {
+        if ![16, 32, 64].contains(&seed.len()) {
+            return Err(Error::BadSeed(seed.len()));
+        }
+
+        Self::from_seed(seed)
+    }
+
+    /// Creates a new `StacksWallet` from a 64 byte BIP39 seed, as returned by
+    /// `Mnemonic::to_seed`.
+    pub fn from_bip39_seed(seed: &[u8; 64]) -> Result<Self, Error> {
+        Self::from_seed_bytes(seed)
+    }
+
+    /// Creates a new `StacksWallet` from entropy of any length.
+    ///
+    /// The entropy is hashed with SHA-512 into a 64 byte seed, the same entropy
+    /// always derives the same wallet. This is not BIP39 compatible, the wallet
+    /// can't be recovered from a mnemonic & is only as strong as the entropy.
+    pub fn from_entropy(entropy: &[u8]) -> Result<Self, Error> {
+        Self::from_seed_bytes(digest(&SHA512, entropy).as_ref())
+    }
+
+    /// Generates a new `StacksWallet` from fresh entropy.
+    ///
+    /// Accepts 12, 15, 18, 21 or 24 words, the returned mnemonic is the only
+    /// way to recover the wallet & should be displayed to the user once.
+    pub fn generate(word_count: usize) -> Result<(Self, Mnemonic), Error> {
+        if !(12..=24).contains(&word_count) || !word_count.is_multiple_of(3) {
+            return Err(Error::InvalidMnemonic(bip39::Error::BadWordCount(
+                word_count,
+            )));
+        }
+
+        let mut entropy = [0u8; 32];
+        let entropy = &mut entropy[..word_count / 3 * 4];
+        SystemRandom::new()
+            .fill(entropy)
+            .map_err(|_| Error::BadEntropy)?;
+
+        let mnemonic = Mnemonic::from_entropy(entropy).map_err(Error::InvalidMnemonic)?;
+        let seed = Zeroizing::new(mnemonic.to_seed_normalized(""));
+        let wallet = Self::from_seed_bytes(seed.as_ref())?;
+        Ok((wallet, mnemonic))
     }
 
     /// Gets an account by derivation index.
@@ -120,6 +329,37 @@ impl StacksWallet {
         }
     }
 
+    /// Lazily derives the accounts over a range of derivation indices.
+    ///
+    /// Each account is derived from the same account-level key, the derived
+    /// accounts are not cached.
+    pub fn accounts(
+        &self,
+        range: Range<u32>,
+    ) -> impl Iterator<Item = Result<StacksAccount, Error>> + '_ {
+        range.map(|index| StacksAccount::derive(index, &self.ext))
+    }
+
+    /// Derives a single normal or hardened child of the root key.
+    ///
+    /// Unlike `get_account`, the derived account is not cached.
+    pub fn derive_child(&self, index: u32, hardened: bool) -> Result<StacksAccount, Error> {
+        let child = self.ext.derive_child(ChildNumber::new(index, hardened)?)?;
+        Ok(StacksAccount::new(index, child))
+    }
+
+    /// Derives the identity data key of an account, at `m/888'/0'/{account}'`.
+    ///
+    /// This is the key Stacks Connect & Gaia use for app private keys, it is not
+    /// related to the account's STX key. The key is derived from the seed on demand.
+    pub fn get_data_key(&self, account: u32) -> Result<SecretKey, Error> {
+        let index = ChildNumber::new(account, true)?;
+        let path = f!("{DATA_DERIVATION_PATH}/{index}");
+        let child = XPrv::derive_from_path(&*self.seed.0, &DerivationPath::from_str(&path)?)?;
+
+        Ok(SecretKey::from_slice(&child.private_key().to_bytes())?)
+    }
+
     /// Returns the private key of the wallet.
     pub fn private_key(&self) -> Result<SecretKey, Error> {
         Ok(SecretKey::from_slice(&self.ext.private_key().to_bytes())?)
@@ -130,15 +370,305 @@ impl StacksWallet {
         Ok(PublicKey::from_slice(&self.ext.public_key().to_bytes())?)
     }
 
+    /// Returns the BIP32 identifier of the extended key, the `Hash160` of its public key.
+    pub fn identifier(&self) -> Hash160 {
+        key_identifier(&self.ext)
+    }
+
+    /// Returns the BIP32 fingerprint of the extended key, the first 4 bytes of its identifier.
+    pub fn fingerprint(&self) -> [u8; 4] {
+        key_fingerprint(&self.ext)
+    }
+
     /// Sets an account by derivation index.
     pub fn set_account(&mut self, index: u32, account: StacksAccount) {
         self.accounts.insert(index, account);
     }
+
+    /// Encrypts the seed of the wallet into a keystore JSON string.
+    ///
+    /// The key is derived from the password with scrypt & the seed is sealed
+    /// with AES-256-GCM, the salt, nonce and KDF parameters are stored
+    /// alongside the ciphertext.
+    #[cfg(feature = "keystore")]
+    pub fn to_encrypted_json(&self, password: &str) -> Result<String, Error> {
+        self.to_encrypted_json_with_cost(password, KEYSTORE_SCRYPT_LOG_N)
+    }
+
+    /// Encrypts the seed of the wallet into a keystore JSON string, with a scrypt
+    /// cost parameter of `N = 2^log_n`.
+    #[cfg(feature = "keystore")]
+    fn to_encrypted_json_with_cost(&self, password: &str, log_n: u8) -> Result<String, Error> {
+        let rng = SystemRandom::new();
+        let mut salt = [0u8; KEYSTORE_SALT_LEN];
+        let mut nonce = [0u8; KEYSTORE_NONCE_LEN];
+
+        rng.fill(&mut salt)
+            .map_err(|_| Error::BadKeystore("failed to generate salt".into()))?;
+        rng.fill(&mut nonce)
+            .map_err(|_| Error::BadKeystore("failed to generate nonce".into()))?;
+
+        let params = KdfParams {
+            log_n,
+            r: KEYSTORE_SCRYPT_R,
+            p: KEYSTORE_SCRYPT_P,
+            dklen: KEYSTORE_KEY_LEN,
+            salt: bytes_to_hex(salt),
+        };
+
+        let cipher = Aes256Gcm::new_from_slice(params.derive_key(password, &salt)?.as_ref())
+            .map_err(|_| Error::BadKeystore("invalid key length".into()))?;
+
+        let plaintext = Zeroizing::new(bytes_to_hex(&*self.seed.0));
+
+        let ciphertext = cipher
+            .encrypt(&Nonce::from(nonce), plaintext.as_bytes())
+            .map_err(|_| Error::BadKeystore("failed to encrypt".into()))?;
+
+        let keystore = Keystore {
+            version: KEYSTORE_VERSION,
+            crypto: KeystoreCrypto {
+                cipher: KEYSTORE_CIPHER.into(),
+                ciphertext: bytes_to_hex(ciphertext),
+                nonce: bytes_to_hex(nonce),
+                kdf: KEYSTORE_KDF.into(),
+                kdfparams: params,
+            },
+        };
+
+        serde_json::to_string(&keystore).map_err(|err| Error::BadKeystore(err.to_string()))
+    }
+
+    /// Decrypts a `StacksWallet` from a keystore JSON string.
+    ///
+    /// Scrypt parameters above `N = 2^18, r = 8, p = 4` are rejected.
+    #[cfg(feature = "keystore")]
+    pub fn from_encrypted_json<S>(json: S, password: &str) -> Result<Self, Error>
+    where
+        S: AsRef<str>,
+    {
+        let keystore = serde_json::from_str::<Keystore>(json.as_ref())
+            .map_err(|err| Error::BadKeystore(err.to_string()))?;
+
+        let crypto = keystore.crypto;
+
+        if keystore.version != KEYSTORE_VERSION {
+            let msg = format!("unsupported version {}", keystore.version);
+            return Err(Error::BadKeystore(msg));
+        }
+
+        if crypto.kdf != KEYSTORE_KDF || crypto.cipher != KEYSTORE_CIPHER {
+            let msg = format!("unsupported scheme {}/{}", crypto.kdf, crypto.cipher);
+            return Err(Error::BadKeystore(msg));
+        }
+
+        let decode =
+            |hex: &str| hex_to_bytes(hex).map_err(|err| Error::BadKeystore(err.to_string()));
+
+        let salt = decode(&crypto.kdfparams.salt)?;
+        let nonce = decode(&crypto.nonce)?;
+        let ciphertext = decode(&crypto.ciphertext)?;
+
+        let nonce: [u8; KEYSTORE_NONCE_LEN] = nonce
+            .try_into()
+            .map_err(|_| Error::BadKeystore("invalid nonce length".into()))?;
+
+        let key = crypto.kdfparams.derive_key(password, &salt)?;
+        let cipher = Aes256Gcm::new_from_slice(key.as_ref())
+            .map_err(|_| Error::BadKeystore("invalid key length".into()))?;
+
+        let plaintext = Zeroizing::new(
+            cipher
+                .decrypt(&Nonce::from(nonce), ciphertext.as_ref())
+                .map_err(|_| Error::BadPassword)?,
+        );
+
+        let plaintext =
+            std::str::from_utf8(&plaintext).map_err(|err| Error::BadKeystore(err.to_string()))?;
+
+        let seed = Zeroizing::new(hex_to_bytes(plaintext)?);
+        Self::from_seed_bytes(&seed)
+    }
+}
+
+/// Parses a private key from a hex string.
+///
+/// Accepts 32 bytes, or 33 bytes with the trailing `01` compression suffix
+/// used by Stacks wallets.
+pub fn private_key_from_hex(hex: &str) -> Result<SecretKey, Error> {
+    let bytes = hex_to_bytes(hex)?;
+    private_key_from_bytes(&bytes)
+}
+
+/// Parses a private key from a Wallet Import Format (WIF) string.
+///
+/// Accepts mainnet (`0x80`) & testnet (`0xef`) keys, compressed or not.
+pub fn private_key_from_wif(wif: &str) -> Result<SecretKey, Error> {
+    if !(51..=52).contains(&wif.len()) {
+        return Err(Error::BadPrivateKey(f!("WIF length {}", wif.len())));
+    }
+
+    let (bytes, version) = base58check_decode(wif)?;
+
+    if version != WIF_VERSION_MAINNET && version != WIF_VERSION_TESTNET {
+        return Err(Error::BadPrivateKey(f!("WIF version {version}")));
+    }
+
+    private_key_from_bytes(&bytes)
+}
+
+/// Parses a private key from 32 bytes or 33 bytes with a compression suffix.
+fn private_key_from_bytes(bytes: &[u8]) -> Result<SecretKey, Error> {
+    match bytes.len() {
+        32 => Ok(SecretKey::from_slice(bytes)?),
+        33 if bytes[32] == PRIVATE_KEY_COMPRESSED => Ok(SecretKey::from_slice(&bytes[..32])?),
+        len => Err(Error::BadPrivateKey(f!("length {len}"))),
+    }
+}
+
+/// Returns the BIP32 identifier of an extended key, the `Hash160` of its public key.
+fn key_identifier(ext: &XPrv) -> Hash160 {
+    Hash160::from_slice(ext.public_key().to_bytes())
+}
+
+/// Returns the BIP32 fingerprint of an extended key, the first 4 bytes of its identifier.
+fn key_fingerprint(ext: &XPrv) -> [u8; 4] {
+    let mut buff = [0u8; 4];
+    buff.copy_from_slice(&key_identifier(ext).as_bytes()[..4]);
+    buff
+}
+
+/// An encrypted keystore holding the seed of a `StacksWallet`.
+#[cfg(feature = "keystore")]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keystore {
+    /// The keystore format version.
+    pub version: u8,
+    /// The encryption parameters & ciphertext.
+    pub crypto: KeystoreCrypto,
+}
+
+/// The encryption section of a `Keystore`.
+#[cfg(feature = "keystore")]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeystoreCrypto {
+    /// The cipher used to seal the key.
+    pub cipher: String,
+    /// The hex encoded ciphertext, including the authentication tag.
+    pub ciphertext: String,
+    /// The hex encoded cipher nonce.
+    pub nonce: String,
+    /// The key derivation function.
+    pub kdf: String,
+    /// The key derivation parameters.
+    pub kdfparams: KdfParams,
+}
+
+/// The scrypt parameters of a `Keystore`.
+#[cfg(feature = "keystore")]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    /// The cost parameter (log2 of N).
+    pub log_n: u8,
+    /// The block size parameter.
+    pub r: u32,
+    /// The parallelization parameter.
+    pub p: u32,
+    /// The length of the derived key.
+    pub dklen: usize,
+    /// The hex encoded salt.
+    pub salt: String,
+}
+
+#[cfg(feature = "keystore")]
+impl KdfParams {
+    /// Derives the encryption key from a password.
+    ///
+    /// The parameters come from untrusted JSON, costs above the maximum are rejected
+    /// before any work is done.
+    fn derive_key(&self, password: &str, salt: &[u8]) -> Result<Zeroizing<Vec<u8>>, Error> {
+        if self.log_n > KEYSTORE_SCRYPT_MAX_LOG_N
+            || self.r > KEYSTORE_SCRYPT_MAX_R
+            || self.p > KEYSTORE_SCRYPT_MAX_P
+        {
+            let msg = f!(
+                "scrypt cost too high, log_n {} r {} p {}",
+                self.log_n,
+                self.r,
+                self.p
+            );
+            return Err(Error::BadKeystore(msg));
+        }
+
+        if self.dklen != KEYSTORE_KEY_LEN {
+            return Err(Error::BadKeystore(f!("invalid dklen {}", self.dklen)));
+        }
+
+        let params = scrypt::Params::new(self.log_n, self.r, self.p, self.dklen)
+            .map_err(|err| Error::BadKeystore(err.to_string()))?;
+
+        let mut key = Zeroizing::new(vec![0u8; self.dklen]);
+        scrypt::scrypt(password.as_bytes(), salt, &params, &mut key)
+            .map_err(|err| Error::BadKeystore(err.to_string()))?;
+
+        Ok(key)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::crypto::bytes_to_hex;
+
+    #[test]
+    fn test_wallet_private_key_from_hex_and_wif() {
+        let secp = crate::crypto::secp256k1_context();
+        let hex = "0c28fca386c7a227600b2fe50b7cae11ec86d3bf1fbe471be89827e19d72aa1d";
+        let expected_pk = "02d0de0aaeaefad02b8bdc8a01a1b8b11c696bd3d66a2c5f10780d95b7df42645c";
+
+        let from_hex = private_key_from_hex(hex).unwrap();
+        let from_hex_compressed = private_key_from_hex(&f!("{hex}01")).unwrap();
+        let from_wif =
+            private_key_from_wif("KwdMAjGmerYanjeui5SHS7JkmpZvVipYvB2LJGU1ZxJwYvP98617").unwrap();
+        let from_wif_uncompressed =
+            private_key_from_wif("5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTJ").unwrap();
+        let from_wif_testnet =
+            private_key_from_wif("cMzLdeGd5vEqxB8B6VFQoRopQ3sLAAvEzDAoQgvX54xwofSWj1fx").unwrap();
+
+        for key in [
+            from_hex,
+            from_hex_compressed,
+            from_wif,
+            from_wif_uncompressed,
+            from_wif_testnet,
+        ] {
+            let pk = bytes_to_hex(key.public_key(secp).serialize());
+            assert_eq!(pk, expected_pk);
+        }
+    }
+
+    #[test]
+    fn test_wallet_private_key_invalid() {
+        let hex = "0c28fca386c7a227600b2fe50b7cae11ec86d3bf1fbe471be89827e19d72aa1d";
+
+        assert!(matches!(
+            private_key_from_hex(&hex[2..]),
+            Err(Error::BadPrivateKey(_))
+        ));
+        assert!(matches!(
+            private_key_from_hex(&f!("{hex}02")),
+            Err(Error::BadPrivateKey(_))
+        ));
+        assert!(matches!(private_key_from_hex("zz"), Err(Error::Hex(_))));
+        assert!(matches!(
+            private_key_from_wif("5Hue"),
+            Err(Error::BadPrivateKey(_))
+        ));
+        assert!(matches!(
+            private_key_from_wif("KwdMAjGmerYanjeui5SHS7JkmpZvVipYvB2LJGU1ZxJwYvP98618"),
+            Err(Error::Base58(_))
+        ));
+    }
 
     #[test]
     fn test_wallet_generate_address() {
@@ -161,6 +691,24 @@ mod tests {
         assert_eq!(testnet_p2sh, expected_testnet_p2sh);
     }
 
+    #[test]
+    #[cfg(feature = "transaction")]
+    fn test_wallet_account_principal() {
+        use crate::clarity::Codec;
+
+        let mut wallet = generate_wallet();
+        let account = wallet.get_account(0).unwrap();
+
+        let mainnet = account.principal(TransactionVersion::Mainnet).unwrap();
+        let expected = PrincipalStandard::new(account.get_address(Version::MainnetP2PKH).unwrap());
+        assert_eq!(mainnet.encode().unwrap(), expected.encode().unwrap());
+        assert_eq!(mainnet.value(), "SP384CVPNDTYA0E92TKJZQTYXQHNZSWGCAG7SAPVB");
+
+        let testnet = account.principal(TransactionVersion::Testnet).unwrap();
+        let expected = PrincipalStandard::new(account.get_address(Version::TestnetP2PKH).unwrap());
+        assert_eq!(testnet.encode().unwrap(), expected.encode().unwrap());
+    }
+
     #[test]
     fn test_wallet_generate_account_indexed() {
         let mut wallet = generate_wallet();
@@ -182,6 +730,340 @@ mod tests {
         assert_eq!(testnet_p2sh, expected_testnet_p2sh);
     }
 
+    #[test]
+    fn test_wallet_from_mnemonic_passphrase() {
+        let secret_key = "sound idle panel often situate develop unit text design antenna vendor screen opinion balcony share trigger accuse scatter visa uniform brass update opinion media";
+
+        let mut plain = StacksWallet::from_mnemonic(secret_key, "").unwrap();
+        let mut protected = StacksWallet::from_mnemonic(secret_key, "hunter2").unwrap();
+
+        let plain_key = plain.get_account(0).unwrap().private_key().unwrap();
+        let protected_key = protected.get_account(0).unwrap().private_key().unwrap();
+        assert_ne!(plain_key, protected_key);
+
+        let mut default = generate_wallet();
+        let default_key = default.get_account(0).unwrap().private_key().unwrap();
+        assert_eq!(plain_key, default_key);
+    }
+
+    #[test]
+    fn test_wallet_from_mnemonic_word_counts() {
+        let phrases = [
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art",
+        ];
+
+        for phrase in phrases {
+            assert!(StacksWallet::from_mnemonic(phrase, "").is_ok());
+        }
+    }
+
+    #[test]
+    fn test_wallet_from_mnemonic_invalid() {
+        let bad_checksum = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon";
+        let result = StacksWallet::from_mnemonic(bad_checksum, "");
+        assert!(matches!(
+            result,
+            Err(Error::InvalidMnemonic(bip39::Error::InvalidChecksum))
+        ));
+
+        let bad_count = "abandon abandon abandon";
+        let result = StacksWallet::from_mnemonic(bad_count, "");
+        assert!(matches!(result, Err(Error::InvalidMnemonic(_))));
+
+        let result = StacksWallet::from_secret_key(bad_checksum);
+        assert!(matches!(
+            result,
+            Err(Error::Bip39(bip39::Error::InvalidChecksum))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "keystore")]
+    fn test_wallet_encrypted_json_roundtrip() {
+        let mut wallet = generate_wallet();
+        let json = wallet.to_encrypted_json_with_cost("password", 10).unwrap();
+
+        let mut restored = StacksWallet::from_encrypted_json(&json, "password").unwrap();
+
+        let expected = wallet.get_account(0).unwrap();
+        let account = restored.get_account(0).unwrap();
+
+        assert_eq!(
+            account.get_address(Version::MainnetP2PKH).unwrap(),
+            expected.get_address(Version::MainnetP2PKH).unwrap()
+        );
+        assert_eq!(restored, wallet);
+    }
+
+    #[test]
+    #[cfg(feature = "keystore")]
+    fn test_wallet_encrypted_json_bad_password() {
+        let wallet = generate_wallet();
+        let json = wallet.to_encrypted_json_with_cost("password", 10).unwrap();
+
+        let result = StacksWallet::from_encrypted_json(&json, "wrong");
+        assert_eq!(result, Err(Error::BadPassword));
+
+        let result = StacksWallet::from_encrypted_json("{}", "password");
+        assert!(matches!(result, Err(Error::BadKeystore(_))));
+    }
+
+    #[test]
+    fn test_wallet_derive_child() {
+        let phrase = "sound idle panel often situate develop unit text design antenna vendor screen opinion balcony share trigger accuse scatter visa uniform brass update opinion media";
+        let wallet = StacksWallet::from_secret_key(phrase).unwrap();
+
+        let seed = bip39::Mnemonic::parse(phrase)
+            .unwrap()
+            .to_seed_normalized("");
+        let derive = |path: &str| {
+            let path = DerivationPath::from_str(&f!("{STX_DERIVATION_PATH}/{path}")).unwrap();
+            let ext = XPrv::derive_from_path(seed, &path).unwrap();
+            SecretKey::from_slice(&ext.private_key().to_bytes()).unwrap()
+        };
+
+        let hardened = wallet.derive_child(2_147_483_647, true).unwrap();
+        assert_eq!(hardened.private_key().unwrap(), derive("2147483647'"));
+
+        let normal = wallet.derive_child(2_147_483_647, false).unwrap();
+        assert_eq!(normal.private_key().unwrap(), derive("2147483647"));
+        assert_ne!(
+            normal.private_key().unwrap(),
+            hardened.private_key().unwrap()
+        );
+
+        let mut cached = wallet.clone();
+        let account = cached.get_account(3).unwrap();
+        assert_eq!(wallet.derive_child(3, false).unwrap(), account);
+
+        assert!(matches!(
+            wallet.derive_child(2_147_483_648, true),
+            Err(Error::Bip32(_))
+        ));
+    }
+
+    #[test]
+    fn test_wallet_from_seed_bytes() {
+        let phrase = "sound idle panel often situate develop unit text design antenna vendor screen opinion balcony share trigger accuse scatter visa uniform brass update opinion media";
+        let seed = Mnemonic::parse(phrase).unwrap().to_seed_normalized("");
+
+        let mut wallet = StacksWallet::from_seed_bytes(&seed).unwrap();
+        let account = wallet.get_account(0).unwrap();
+        assert_eq!(
+            account.get_address(Version::MainnetP2PKH).unwrap(),
+            generate_wallet()
+                .get_account(0)
+                .unwrap()
+                .get_address(Version::MainnetP2PKH)
+                .unwrap()
+        );
+
+        let short = hex_to_bytes("000102030405060708090a0b0c0d0e0f").unwrap();
+        let path = DerivationPath::from_str(&f!("{STX_DERIVATION_PATH}/0")).unwrap();
+        let expected = XPrv::derive_from_path(&short, &path).unwrap();
+        let mut wallet = StacksWallet::from_seed_bytes(&short).unwrap();
+        assert_eq!(
+            wallet.get_account(0).unwrap().private_key().unwrap(),
+            SecretKey::from_slice(&expected.private_key().to_bytes()).unwrap()
+        );
+
+        assert_eq!(
+            StacksWallet::from_seed_bytes(&[0u8; 20]),
+            Err(Error::BadSeed(20))
+        );
+    }
+
+    #[test]
+    fn test_wallet_from_bip39_seed_and_entropy() {
+        let phrase = "sound idle panel often situate develop unit text design antenna vendor screen opinion balcony share trigger accuse scatter visa uniform brass update opinion media";
+        let seed = Mnemonic::parse(phrase).unwrap().to_seed_normalized("");
+
+        assert_eq!(
+            StacksWallet::from_bip39_seed(&seed).unwrap(),
+            StacksWallet::from_seed_bytes(&seed).unwrap()
+        );
+
+        let entropy = [0xab; 20];
+        let mut wallet = StacksWallet::from_entropy(&entropy).unwrap();
+        let mut again = StacksWallet::from_entropy(&entropy).unwrap();
+        assert_eq!(
+            wallet.get_account(0).unwrap().private_key().unwrap(),
+            again.get_account(0).unwrap().private_key().unwrap()
+        );
+
+        let seed: [u8; 64] = digest(&SHA512, &entropy).as_ref().try_into().unwrap();
+        assert_eq!(
+            StacksWallet::from_entropy(&entropy).unwrap(),
+            StacksWallet::from_bip39_seed(&seed).unwrap()
+        );
+
+        let mut other = StacksWallet::from_entropy(&[0xab; 21]).unwrap();
+        assert_ne!(
+            wallet.get_account(0).unwrap().private_key().unwrap(),
+            other.get_account(0).unwrap().private_key().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_wallet_generate() {
+        let (mut wallet, mnemonic) = StacksWallet::generate(24).unwrap();
+        assert_eq!(mnemonic.word_count(), 24);
+
+        let mut imported = StacksWallet::from_mnemonic(mnemonic.to_string(), "").unwrap();
+        assert_eq!(
+            wallet
+                .get_account(0)
+                .unwrap()
+                .get_address(Version::MainnetP2PKH),
+            imported
+                .get_account(0)
+                .unwrap()
+                .get_address(Version::MainnetP2PKH)
+        );
+
+        let (_, short) = StacksWallet::generate(12).unwrap();
+        assert_eq!(short.word_count(), 12);
+        assert_ne!(short.to_string(), mnemonic.to_string());
+
+        for count in [0, 11, 13, 27] {
+            assert!(matches!(
+                StacksWallet::generate(count),
+                Err(Error::InvalidMnemonic(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_wallet_accounts_range() {
+        let mut wallet = generate_wallet();
+        let parent = wallet.ext.public_key().fingerprint();
+
+        let accounts = wallet
+            .accounts(0..5)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(accounts.len(), 5);
+
+        for (index, account) in (0..5).zip(&accounts) {
+            let expected = wallet.get_account(index).unwrap();
+            assert_eq!(account.index, index);
+            assert_eq!(account.ext.attrs().parent_fingerprint, parent);
+            assert_eq!(
+                account.get_address(Version::MainnetP2PKH).unwrap(),
+                expected.get_address(Version::MainnetP2PKH).unwrap()
+            );
+        }
+
+        assert_eq!(
+            accounts[1].get_address(Version::MainnetP2PKH).unwrap(),
+            "SP23K7K2V45JFZVBMQBE8R0PP8SQG7HZF9473KBD"
+        );
+        assert_eq!(wallet.accounts(3..3).count(), 0);
+    }
+
+    #[test]
+    fn test_wallet_accounts_public_key_cached_context() {
+        let wallet = generate_wallet();
+        let secp = crate::crypto::secp256k1_context();
+
+        for index in 0..5 {
+            let account = wallet.derive_child(index, false).unwrap();
+            let derived = account.private_key().unwrap().public_key(secp);
+            assert_eq!(derived, account.public_key().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_wallet_get_data_key() {
+        // The data address of account 0 for this phrase, from the stacks.js wallet-sdk tests.
+        let wallet = generate_wallet();
+        let key = wallet.get_data_key(0).unwrap();
+        let hash = Hash160::from_slice(
+            key.public_key(crate::crypto::secp256k1_context())
+                .serialize(),
+        );
+        assert_eq!(
+            crate::crypto::base58check_encode(hash.as_bytes(), 0),
+            "1JeTQ5cQjsD57YGcsVFhwT7iuQUXJR6BSk"
+        );
+
+        assert_ne!(wallet.get_data_key(0), wallet.get_data_key(1));
+        assert_ne!(
+            wallet.get_data_key(0).unwrap(),
+            wallet
+                .clone()
+                .get_account(0)
+                .unwrap()
+                .private_key()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "keystore")]
+    fn test_wallet_encrypted_json_seed() {
+        let wallet = generate_wallet();
+        let json = wallet.to_encrypted_json_with_cost("password", 10).unwrap();
+
+        let keystore = serde_json::from_str::<Keystore>(&json).unwrap();
+        assert_eq!(keystore.version, KEYSTORE_VERSION);
+
+        // The seed is sealed, so the restored wallet derives other roots, e.g. data keys.
+        let restored = StacksWallet::from_encrypted_json(&json, "password").unwrap();
+        assert_eq!(restored.seed, wallet.seed);
+        assert_eq!(restored.get_data_key(0), wallet.get_data_key(0));
+        assert!(!f!("{restored:?}").contains(&bytes_to_hex(&*wallet.seed.0)));
+    }
+
+    #[test]
+    #[cfg(feature = "keystore")]
+    fn test_wallet_encrypted_json_scrypt_bounds() {
+        let json = generate_wallet()
+            .to_encrypted_json_with_cost("password", 10)
+            .unwrap();
+
+        let tamper = |set: fn(&mut KdfParams)| {
+            let mut keystore = serde_json::from_str::<Keystore>(&json).unwrap();
+            set(&mut keystore.crypto.kdfparams);
+            serde_json::to_string(&keystore).unwrap()
+        };
+
+        for json in [
+            tamper(|params| params.log_n = 63),
+            tamper(|params| params.r = u32::MAX),
+            tamper(|params| params.p = u32::MAX),
+            tamper(|params| params.dklen = 1 << 30),
+        ] {
+            let result = StacksWallet::from_encrypted_json(&json, "password");
+            assert!(matches!(result, Err(Error::BadKeystore(_))));
+        }
+    }
+
+    #[test]
+    fn test_wallet_fingerprint() {
+        let seed = hex_to_bytes("000102030405060708090a0b0c0d0e0f").unwrap();
+        let master = StacksWallet::new(
+            XPrv::new(&seed).unwrap(),
+            StacksAccounts::new(),
+            Seed(Zeroizing::new(seed.clone())),
+        );
+
+        assert_eq!(
+            master.identifier().hex(),
+            "3442193e1bb70916e914552172cd4e2dbc9df811"
+        );
+        assert_eq!(master.fingerprint(), [0x34, 0x42, 0x19, 0x3e]);
+
+        let child = master.derive_child(0, true).unwrap();
+        assert_eq!(
+            child.identifier().hex(),
+            "5c1bd648ed23aa5fd50ba52b2457c11e9e80a6a7"
+        );
+        assert_eq!(child.fingerprint(), [0x5c, 0x1b, 0xd6, 0x48]);
+        assert_eq!(child.ext.attrs().parent_fingerprint, master.fingerprint());
+    }
+
     fn generate_wallet() -> StacksWallet {
         let secret_key = "sound idle panel often situate develop unit text design antenna vendor screen opinion balcony share trigger accuse scatter visa uniform brass update opinion media";
         StacksWallet::from_secret_key(secret_key).unwrap()
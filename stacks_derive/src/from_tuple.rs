@@ -43,7 +43,7 @@ pub(crate) struct FieldRecv {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// #[derive(FromTuple)]
     /// struct Data {
     ///     #[stacks(key = "some-key")]
@@ -55,7 +55,7 @@ pub(crate) struct FieldRecv {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// #[derive(FromTuple)]
     /// struct Data {
     ///     #[stacks(key = "some-key", response)]
@@ -63,6 +63,18 @@ pub(crate) struct FieldRecv {
     /// }
     /// ```
     response: Option<bool>,
+    /// Indicate whether a missing key falls back to `Default::default()`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// #[derive(FromTuple)]
+    /// struct Data {
+    ///     #[stacks(key = "some-key", default)]
+    ///     a: u128,
+    /// }
+    /// ```
+    default: Option<bool>,
 }
 
 /// The internal error type for the `FromTuple` implementation.
@@ -123,6 +135,10 @@ pub(crate) fn __impl(input: proc_macro::TokenStream) -> Result<TokenStream> {
     }
 
     for field in &fields {
+        if field.ident.is_some() && __internal_is_phantom(&field.ty) {
+            continue;
+        }
+
         if field.key.is_none() || field.ident.is_none() {
             return Err(Error::new_spanned(
                 field.ident.to_token_stream(),
@@ -135,6 +151,12 @@ pub(crate) fn __impl(input: proc_macro::TokenStream) -> Result<TokenStream> {
     for field in &fields {
         let FieldRecv { ident, key, .. } = field;
 
+        // marker fields carry no data, e.g. for otherwise unused generics
+        if __internal_is_phantom(&field.ty) {
+            tokens.push(quote!(#ident: ::std::marker::PhantomData));
+            continue;
+        }
+
         let mut stream = TokenStream::new();
 
         let key = key.to_token_stream();
@@ -210,6 +232,13 @@ pub(crate) fn __impl(input: proc_macro::TokenStream) -> Result<TokenStream> {
                         break 'stream;
                     }
 
+                    // handle Vec<T: TryFrom<Tuple>>
+                    if ident.eq("Vec") {
+                        __internal_extract_and_cast(&mut stream, &key, &ty_name);
+                        __internal_derive_list_tuple(&mut stream, args, &key, &ident.to_token_stream());
+                        break 'stream;
+                    }
+
                     // handle Option<i128>
                     if ident.eq("Option") && args.to_string().eq("i128") {
                         __internal_extract_and_cast(&mut stream, &key, &ty_name);
@@ -237,6 +266,13 @@ pub(crate) fn __impl(input: proc_macro::TokenStream) -> Result<TokenStream> {
                         stream.extend(tokens);
                         break 'stream;
                     }
+
+                    // handle Option<T: TryFrom<Tuple>>
+                    if ident.eq("Option") {
+                        __internal_extract_and_cast(&mut stream, &key, &ty_name);
+                        __internal_derive_option_tuple(&mut stream, args, &key, &ident.to_token_stream());
+                        break 'stream;
+                    }
                 }
 
                 // handle standalone T: TryFrom<Tuple>
@@ -244,21 +280,33 @@ pub(crate) fn __impl(input: proc_macro::TokenStream) -> Result<TokenStream> {
                 __internal_extract_and_cast(&mut stream, &key, &ty_name);
                 __internal_err_unwrap(&mut stream);
                 __internal_type_cast(&mut stream, &quote!(Tuple), &key, &ident.into_token_stream());
-                stream.extend(quote!(.and_then(#ident::try_from)?));
+                stream.extend(quote!(.and_then(<#tp>::try_from)?));
                 break 'stream;
                 }
 
                 return Err(Error::new_spanned(
                     tp.to_token_stream(),
-                    "Unsupported type, did you mean ::std::vec::Vec<u8>, Option<T> or `T: TryFrom<Tuple>`?",
+                    "Unsupported type, did you mean ::std::vec::Vec<u8>, Vec<T>, Option<T> or `T: TryFrom<Tuple>`?",
                 ))
             }
             _ => return Err(Error::new_spanned(
                 field.ty.to_token_stream(),
-                "Unsupported type, expected one of: i128, u128, ::std::vec::Vec<u8>, Option<T>, bool, String, or `T: TryFrom<Tuple>`",
+                "Unsupported type, expected one of: i128, u128, ::std::vec::Vec<u8>, Vec<T>, Option<T>, bool, String, or `T: TryFrom<Tuple>`",
             )),
         };
 
+        // fall back to the default value for missing keys
+        if field.default == Some(true) {
+            let key = field.key.to_token_stream();
+            stream = quote!(
+                if tuple.get(#key).is_some() {
+                    #stream
+                } else {
+                    ::std::default::Default::default()
+                }
+            );
+        }
+
         tokens.push(quote!(
             #ident: #stream
         ))
@@ -353,6 +401,70 @@ where
     })
 }
 
+fn __internal_derive_option_tuple(
+    stream: &mut TokenStream,
+    ty: &TokenStream,
+    key: &TokenStream,
+    ident: &TokenStream,
+) {
+    let some_err = __Error::Cast(key, &quote!(OptionalSome), ident);
+    let tuple_err = __Error::Cast(key, &quote!(Tuple), ident);
+
+    stream.extend(quote! {
+        .and_then(|value| {
+            if value.as_any().is::<::stacks_rs::clarity::OptionalNone>() {
+                Ok(None)
+            } else {
+                value
+                .cast::<::stacks_rs::clarity::OptionalSome>()
+                .map_err(|_| #some_err)?
+                .into_value()
+                .cast::<::stacks_rs::clarity::Tuple>()
+                .map_err(|_| #tuple_err)
+                .and_then(<#ty>::try_from)
+                .map(Some)
+            }
+        })?
+    })
+}
+
+fn __internal_derive_list_tuple(
+    stream: &mut TokenStream,
+    ty: &TokenStream,
+    key: &TokenStream,
+    ident: &TokenStream,
+) {
+    let list_err = __Error::Cast(key, &quote!(List), ident);
+    let tuple_err = __Error::Cast(key, &quote!(Tuple), ident);
+
+    stream.extend(quote! {
+        .and_then(|value| {
+            value
+            .cast::<::stacks_rs::clarity::List>()
+            .map_err(|_| #list_err)?
+            .into_iter()
+            .map(|item| {
+                item
+                .cast::<::stacks_rs::clarity::Tuple>()
+                .map_err(|_| #tuple_err)
+                .and_then(<#ty>::try_from)
+            })
+            .collect::<Result<::std::vec::Vec<_>, _>>()
+        })?
+    })
+}
+
+fn __internal_is_phantom(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(tp) => tp
+            .path
+            .segments
+            .last()
+            .is_some_and(|seg| seg.ident == "PhantomData"),
+        _ => false,
+    }
+}
+
 fn __internal_err_unwrap(stream: &mut TokenStream) {
     stream.extend(quote! {?})
 }
@@ -7,6 +7,7 @@
 // Usage of this file is permitted solely under a sanctioned license.
 
 #![allow(unused_macros, unused_imports, dead_code)]
+#![allow(clippy::bool_assert_comparison, clippy::useless_vec)]
 
 #[path = "./macro.rs"]
 mod macros;
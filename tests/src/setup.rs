@@ -9,14 +9,17 @@
 use stacks_rs::clarity;
 use stacks_rs::clarity::FnArguments;
 use stacks_rs::clarity::PrincipalContract;
+use stacks_rs::clarity::PrincipalStandard;
 use stacks_rs::crypto::hex_to_bytes;
 use stacks_rs::post_condition;
 use stacks_rs::transaction::AssetInfo;
 use stacks_rs::transaction::ConditionCode;
 use stacks_rs::transaction::FungiblePostCondition;
+use stacks_rs::transaction::Network;
 use stacks_rs::transaction::NonFungiblePostCondition;
 use stacks_rs::transaction::PostConditions;
 use stacks_rs::transaction::STXPostCondition;
+use stacks_rs::transaction::STXTokenTransfer;
 use stacks_rs::wallet::StacksWallet;
 use stacks_rs::SecretKey;
 
@@ -27,6 +30,25 @@ pub fn private_key() -> SecretKey {
     SecretKey::from_slice(&pk_bytes).unwrap()
 }
 
+/// Returns a 12345 micro-stacks `STXTokenTransfer` for testing, with default options.
+///
+/// Override options with struct update syntax, e.g.
+/// `STXTokenTransfer { fee: 180, ..token_transfer(StacksMainnet::new()) }`.
+pub fn token_transfer<N>(network: N) -> STXTokenTransfer<PrincipalStandard, N>
+where
+    N: Network,
+{
+    STXTokenTransfer::builder()
+        .recipient(clarity!(
+            PrincipalStandard,
+            "SP3FGQ8Z7JY9BWYZ5WM53E0M9NK7WHJF0691NZ159"
+        ))
+        .network(network)
+        .sender(private_key())
+        .amount(12345)
+        .build()
+}
+
 /// Returns a `PrincipalContract` for testing.
 pub fn contract() -> PrincipalContract {
     clarity!(
@@ -10,6 +10,7 @@ use stacks_rs::clarity;
 use stacks_rs::clarity::Codec;
 use stacks_rs::crypto::bytes_to_hex;
 use stacks_rs::transaction::AnchorMode;
+use stacks_rs::transaction::NamedArguments;
 use stacks_rs::transaction::PostConditionMode;
 use stacks_rs::transaction::PostConditions;
 use stacks_rs::transaction::STXContractCall;
@@ -81,3 +82,168 @@ generate_contract_call_test!(
     "8080000000040015c31b8c1c11c515e244b75806bac48d1399c775000000000000003700000000000186a00001818ebf7aee678097e06fef2e8cea4b061f6b3f5eee2b31d6a21823efa81eaa4224b5a2be1eb33a80ecdf1d433996476efec2f3595411d63b7b2695a1df04f890010200000004000216a5d9d331000f5b79578ce56bd157f29a9056f0d60300000000000f4240000316a5d9d331000f5b79578ce56bd157f29a9056f0d60b6d792d636f6e74726163740100000000000f4240020216a5d9d331000f5b79578ce56bd157f29a9056f0d616a5d9d331000f5b79578ce56bd157f29a9056f0d60b6d792d636f6e7472616374086d792d6173736574010000000000000000000000000000eaf511010316a5d9d331000f5b79578ce56bd157f29a9056f0d60b6d792d636f6e747261637416a5d9d331000f5b79578ce56bd157f29a9056f0d60b6d792d636f6e7472616374086d792d61737365740500000000000f42400216df0ba3e79792be7be5e50a370289accfc8c9e032076578616d706c650d66756e6374696f6e2d6e616d650000000f000000000000000000000000000000000300fffffffffffffffffffffffffffffffc01000000000000000000000000000000010304051ae4286e94a0b003fdeb9379af3bcac21ff897936e061ae4286e94a0b003fdeb9379af3bcac21ff897936e09746573742d6e616d650a0000000000000000000000000000000001090700000000000000000000000000000000010800000000000000000000000000000000010c000000020568656c6c6f0000000000000000000000000000000001017801000000000000000000000000000000020200000004deadbeef0d0000000b68656c6c6f20776f726c640e0000000968656c6c6f20e188b4",
     "e55849944a1d5f0f5937e1de0e61b30ab44b6afcc8f5b39b969d158bbcdfda73"
 );
+
+#[test]
+fn test_transaction_contract_call_named_arguments() {
+    let params = ["recipient", "amount", "memo"];
+    let recipient = "SP3FGQ8Z7JY9BWYZ5WM53E0M9NK7WHJF0691NZ159";
+
+    let named = NamedArguments::new()
+        .arg("memo", clarity!(OptionalNone))
+        .arg("amount", clarity!(UInt, 100))
+        .arg("recipient", clarity!(PrincipalStandard, recipient))
+        .into_fn_args(&params)
+        .unwrap();
+
+    let positional = clarity!(
+        FnArguments,
+        clarity!(PrincipalStandard, recipient),
+        clarity!(UInt, 100),
+        clarity!(OptionalNone)
+    );
+
+    assert_eq!(named.encode().unwrap(), positional.encode().unwrap());
+
+    let build = |fn_args| {
+        STXContractCall::builder()
+            .address("SP000000000000000000002Q6VF78")
+            .contract("token")
+            .fn_name("transfer")
+            .fn_args(fn_args)
+            .sender(private_key())
+            .network(StacksMainnet::new())
+            .build()
+            .transaction()
+            .unwrap()
+            .encode()
+            .unwrap()
+    };
+    assert_eq!(build(named), build(positional));
+
+    let unknown = NamedArguments::new()
+        .arg("sender", clarity!(UInt, 1))
+        .into_fn_args(&params);
+    assert!(matches!(unknown, Err(clarity::Error::UnknownArgument(name)) if name == "sender"));
+
+    let missing = NamedArguments::new()
+        .arg("amount", clarity!(UInt, 100))
+        .arg("recipient", clarity!(PrincipalStandard, recipient))
+        .into_fn_args(&params);
+    assert!(matches!(missing, Err(clarity::Error::MissingArgument(name)) if name == "memo"));
+
+    let duplicate = NamedArguments::new()
+        .arg("amount", clarity!(UInt, 1))
+        .arg("amount", clarity!(UInt, 2))
+        .into_fn_args(&params);
+    assert!(matches!(duplicate, Err(clarity::Error::DuplicateKey(_))));
+}
+
+#[test]
+fn test_transaction_contract_call_arg_named() {
+    let params = ["recipient", "amount", "memo"];
+    let recipient = "SP3FGQ8Z7JY9BWYZ5WM53E0M9NK7WHJF0691NZ159";
+
+    let named = STXContractCall::builder()
+        .address("SP000000000000000000002Q6VF78")
+        .contract("token")
+        .fn_name("transfer")
+        .fn_params(&params)
+        .arg_named("memo", clarity!(OptionalNone))
+        .arg_named("amount", clarity!(UInt, 100))
+        .arg_named("recipient", clarity!(PrincipalStandard, recipient))
+        .sender(private_key())
+        .network(StacksMainnet::new())
+        .build()
+        .transaction()
+        .unwrap();
+
+    let positional = STXContractCall::builder()
+        .address("SP000000000000000000002Q6VF78")
+        .contract("token")
+        .fn_name("transfer")
+        .fn_args(clarity!(
+            FnArguments,
+            clarity!(PrincipalStandard, recipient),
+            clarity!(UInt, 100),
+            clarity!(OptionalNone)
+        ))
+        .sender(private_key())
+        .network(StacksMainnet::new())
+        .build()
+        .transaction()
+        .unwrap();
+
+    assert_eq!(named.encode().unwrap(), positional.encode().unwrap());
+
+    let missing = STXContractCall::builder()
+        .address("SP000000000000000000002Q6VF78")
+        .contract("token")
+        .fn_name("transfer")
+        .fn_params(&params)
+        .arg_named("amount", clarity!(UInt, 100))
+        .sender(private_key())
+        .network(StacksMainnet::new())
+        .build()
+        .transaction();
+    assert!(matches!(missing, Err(clarity::Error::MissingArgument(name)) if name == "recipient"));
+
+    let mixed = STXContractCall::builder()
+        .address("SP000000000000000000002Q6VF78")
+        .contract("token")
+        .fn_name("transfer")
+        .fn_args(clarity!(FnArguments, clarity!(UInt, 100)))
+        .fn_params(&params)
+        .arg_named("amount", clarity!(UInt, 100))
+        .sender(private_key())
+        .network(StacksMainnet::new())
+        .build()
+        .transaction();
+    assert!(matches!(mixed, Err(clarity::Error::MixedArguments)));
+}
+
+#[test]
+fn test_transaction_contract_call_note() {
+    let build = |note: Option<&str>| {
+        let builder = STXContractCall::builder()
+            .address("SP000000000000000000002Q6VF78")
+            .contract("token")
+            .fn_name("transfer")
+            .fn_args(clarity!(FnArguments, clarity!(UInt, 100)))
+            .sender(private_key())
+            .network(StacksMainnet::new());
+
+        match note {
+            Some(note) => builder.note(note).build().transaction(),
+            None => builder.build().transaction(),
+        }
+        .unwrap()
+        .sign(private_key())
+        .unwrap()
+    };
+
+    let noted = build(Some("invoice #42"));
+    let plain = build(None);
+
+    assert_eq!(noted.note.as_deref(), Some("invoice #42"));
+    assert_eq!(plain.note, None);
+    assert_eq!(noted.encode().unwrap(), plain.encode().unwrap());
+    assert_eq!(noted.hash().unwrap(), plain.hash().unwrap());
+
+    assert_eq!(noted.to_json()["note"], "invoice #42");
+    assert!(plain.to_json()["note"].is_null());
+}
+
+#[test]
+fn test_transaction_contract_call_total_cost() {
+    let call = STXContractCall::builder()
+        .address("SP000000000000000000002Q6VF78")
+        .contract("token")
+        .fn_name("transfer")
+        .fn_args(clarity!(FnArguments, clarity!(UInt, 1_000_000)))
+        .sender(private_key())
+        .network(StacksMainnet::new())
+        .fee(3000)
+        .build();
+
+    assert_eq!(call.total_cost(), 3000);
+}
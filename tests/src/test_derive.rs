@@ -158,6 +158,161 @@ fn test_derive_from_tuple() {
     );
 }
 
+#[test]
+fn test_derive_from_tuple_default() {
+    #[derive(FromTuple)]
+    struct Payload {
+        #[stacks(key = "a")]
+        a: u128,
+        #[stacks(key = "b", default)]
+        b: u128,
+        #[stacks(key = "c", default)]
+        c: Option<String>,
+        #[stacks(key = "d", default)]
+        d: bool,
+    }
+
+    let legacy = clarity!(Tuple, ("a", clarity!(UInt, 1)));
+    let parsed = Payload::try_from(legacy).unwrap();
+    assert_eq!(parsed.a, 1);
+    assert_eq!(parsed.b, 0);
+    assert_eq!(parsed.c, None);
+    assert!(!parsed.d);
+
+    let current = clarity!(
+        Tuple,
+        ("a", clarity!(UInt, 1)),
+        ("b", clarity!(UInt, 2)),
+        ("c", clarity!(OptionalSome, clarity!(StringAscii, "x"))),
+        ("d", clarity!(True))
+    );
+    let parsed = Payload::try_from(current).unwrap();
+    assert_eq!(parsed.b, 2);
+    assert_eq!(parsed.c, Some("\"x\"".to_string()));
+    assert!(parsed.d);
+
+    let missing = clarity!(Tuple, ("b", clarity!(UInt, 2)));
+    assert!(matches!(
+        Payload::try_from(missing),
+        Err(derive::Error::Extract(_, _))
+    ));
+}
+
+#[test]
+fn test_derive_from_tuple_optional_nested() {
+    #[derive(FromTuple)]
+    struct Payload {
+        #[stacks(key = "owner")]
+        owner: Option<Owner>,
+        #[stacks(key = "delegate")]
+        delegate: Option<Owner>,
+    }
+
+    #[derive(FromTuple)]
+    struct Owner {
+        #[stacks(key = "id")]
+        id: u128,
+    }
+
+    let data = clarity!(
+        Tuple,
+        (
+            "owner",
+            clarity!(OptionalSome, clarity!(Tuple, ("id", clarity!(UInt, 7))))
+        ),
+        ("delegate", clarity!(OptionalNone))
+    );
+
+    let parsed = Payload::try_from(data).unwrap();
+    assert_eq!(parsed.owner.map(|owner| owner.id), Some(7));
+    assert!(parsed.delegate.is_none());
+
+    let bad = clarity!(
+        Tuple,
+        ("owner", clarity!(OptionalSome, clarity!(UInt, 7))),
+        ("delegate", clarity!(OptionalNone))
+    );
+    assert!(matches!(
+        Payload::try_from(bad),
+        Err(derive::Error::Cast(_, _, _))
+    ));
+}
+
+#[test]
+fn test_derive_from_tuple_list_nested() {
+    #[derive(FromTuple)]
+    struct Registry {
+        #[stacks(key = "owners")]
+        owners: Vec<Owner>,
+        #[stacks(key = "none")]
+        none: Vec<Owner>,
+    }
+
+    #[derive(FromTuple)]
+    struct Owner {
+        #[stacks(key = "id")]
+        id: u128,
+        #[stacks(key = "name")]
+        name: String,
+    }
+
+    let owner = |id: u128, name: &str| {
+        clarity!(
+            Tuple,
+            ("id", clarity!(UInt, id)),
+            ("name", clarity!(StringAscii, name))
+        )
+    };
+    let data = clarity!(
+        Tuple,
+        ("owners", clarity!(List, owner(1, "alice"), owner(2, "bob"))),
+        ("none", clarity!(List))
+    );
+
+    let parsed = Registry::try_from(data).unwrap();
+    assert_eq!(parsed.owners.len(), 2);
+    assert_eq!(parsed.owners[0].id, 1);
+    assert_eq!(parsed.owners[1].id, 2);
+    assert_eq!(parsed.owners[1].name, "\"bob\"");
+    assert!(parsed.none.is_empty());
+
+    let not_tuple = clarity!(
+        Tuple,
+        (
+            "owners",
+            clarity!(List, owner(1, "alice"), clarity!(UInt, 2))
+        ),
+        ("none", clarity!(List))
+    );
+    assert!(matches!(
+        Registry::try_from(not_tuple),
+        Err(derive::Error::Cast(_, ty, _)) if ty == "Tuple"
+    ));
+
+    let not_list = clarity!(
+        Tuple,
+        ("owners", owner(1, "alice")),
+        ("none", clarity!(List))
+    );
+    assert!(matches!(
+        Registry::try_from(not_list),
+        Err(derive::Error::Cast(_, ty, _)) if ty == "List"
+    ));
+
+    let bad_member = clarity!(
+        Tuple,
+        (
+            "owners",
+            clarity!(List, clarity!(Tuple, ("id", clarity!(UInt, 1))))
+        ),
+        ("none", clarity!(List))
+    );
+    assert!(matches!(
+        Registry::try_from(bad_member),
+        Err(derive::Error::Extract(key, _)) if key == "name"
+    ));
+}
+
 #[test]
 fn test_derive_from_tuple_to_string() {
     #[derive(FromTuple)]
@@ -234,3 +389,10 @@ fn test_derive_from_tuple_to_string() {
     assert_eq!(parsed.string_ascii, "\"hello world\"");
     assert_eq!(parsed.string_utf8, "u\"hello ሴ\"");
 }
+
+#[test]
+fn test_derive_from_tuple_generics() {
+    let cases = trybuild::TestCases::new();
+    cases.pass("ui/from_tuple/generic.rs");
+    cases.pass("ui/from_tuple/where_clause.rs");
+}
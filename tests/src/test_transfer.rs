@@ -9,16 +9,29 @@
 use stacks_rs::clarity;
 use stacks_rs::clarity::Codec;
 use stacks_rs::crypto::bytes_to_hex;
+use stacks_rs::crypto::c32::Mode;
+use stacks_rs::crypto::c32::SingleSigMode;
+use stacks_rs::crypto::secp256k1_context;
+use stacks_rs::crypto::SignatureHash;
 use stacks_rs::transaction::AnchorMode;
+use stacks_rs::transaction::Auth;
+use stacks_rs::transaction::Error;
 use stacks_rs::transaction::PostConditionMode;
 use stacks_rs::transaction::PostConditions;
 use stacks_rs::transaction::STXTokenTransfer;
+use stacks_rs::transaction::SpendingConditionStandard;
 use stacks_rs::transaction::StacksMainnet;
+use stacks_rs::transaction::StacksNetwork;
 use stacks_rs::transaction::StacksTestnet;
+use stacks_rs::transaction::TransactionSigner;
+use stacks_rs::transaction::TransactionVersion;
+use stacks_rs::transaction::DEFAULT_MAX_FEE;
+use stacks_rs::SecretKey;
 
 use crate::generate_token_transfer_test;
 use crate::setup::post_conditions;
 use crate::setup::private_key;
+use crate::setup::token_transfer;
 
 generate_token_transfer_test!(
     Standard,
@@ -87,3 +100,403 @@ generate_token_transfer_test!(
     "8080000000040015c31b8c1c11c515e244b75806bac48d1399c775000000000000003700000000000186a00000553a1671243d4bd9032e635a686da1e008cb902166a18c0b1662ca9ec341563d50ed87c3f43c155c6dc513c5633a1092822eac45dc901d1419f0aea40a9c89f5010100000004000216a5d9d331000f5b79578ce56bd157f29a9056f0d60300000000000f4240000316a5d9d331000f5b79578ce56bd157f29a9056f0d60b6d792d636f6e74726163740100000000000f4240020216a5d9d331000f5b79578ce56bd157f29a9056f0d616a5d9d331000f5b79578ce56bd157f29a9056f0d60b6d792d636f6e7472616374086d792d6173736574010000000000000000000000000000eaf511010316a5d9d331000f5b79578ce56bd157f29a9056f0d60b6d792d636f6e747261637416a5d9d331000f5b79578ce56bd157f29a9056f0d60b6d792d636f6e7472616374086d792d61737365740500000000000f4240000516df0ba3e79792be7be5e50a370289accfc8c9e032000000000000303974657374206d656d6f00000000000000000000000000000000000000000000000000",
     "54d8fe95c41bafdcc125126be4c631ba2354ecb8a2743c94be4a0ecb2f92adc0"
 );
+
+#[test]
+fn test_transaction_token_transfer_deterministic_signature() {
+    let transfer = STXTokenTransfer {
+        fee: 100_000,
+        nonce: 55,
+        ..token_transfer(StacksMainnet::new())
+    };
+
+    let tx_1 = transfer.clone().transaction().sign(private_key()).unwrap();
+    let tx_2 = transfer.transaction().sign(private_key()).unwrap();
+
+    assert_eq!(tx_1.encode().unwrap(), tx_2.encode().unwrap());
+    assert_eq!(tx_1.hash().unwrap(), tx_2.hash().unwrap());
+}
+
+#[test]
+fn test_transaction_token_transfer_initial_sighash() {
+    let transfer = STXTokenTransfer {
+        anchor_mode: AnchorMode::Any,
+        memo: "test memo".into(),
+        post_condition_mode: PostConditionMode::Deny,
+        ..token_transfer(StacksMainnet::new())
+    };
+
+    let tx = transfer.transaction();
+    let hash = SignatureHash::from_transaction(&tx).unwrap();
+
+    let signer = TransactionSigner::new(tx.clone()).unwrap();
+    assert_eq!(signer.hash, hash);
+
+    let (signature, _) = SignatureHash::next_signature(hash, 0x04, 0, 0, private_key()).unwrap();
+    let signed = tx.sign(private_key()).unwrap();
+
+    let signed_hex = bytes_to_hex(signed.encode().unwrap());
+    assert!(signed_hex.contains(&bytes_to_hex(signature.as_bytes())));
+    assert_eq!(
+        signed_hex,
+        "0000000001040015c31b8c1c11c515e244b75806bac48d1399c7750000000000000000000000000000000000008b316d56e35b3b8d03ab3b9dbe05eb44d64c53e7ba3c468f9a78c82a13f2174c32facb0f29faeb21075ec933db935ebc28a8793cc60e14b8ee4ef05f52c94016030200000000000516df0ba3e79792be7be5e50a370289accfc8c9e032000000000000303974657374206d656d6f00000000000000000000000000000000000000000000000000"
+    );
+}
+
+#[test]
+fn test_transaction_token_transfer_set_fee_checked() {
+    let transfer = token_transfer(StacksMainnet::new());
+
+    let mut tx = transfer.transaction();
+
+    tx.set_fee_checked(180).unwrap();
+    assert_eq!(tx.auth.origin().fee(), 180);
+
+    tx.set_fee_checked(DEFAULT_MAX_FEE).unwrap();
+    assert_eq!(tx.auth.origin().fee(), DEFAULT_MAX_FEE);
+
+    let err = tx.set_fee_checked(u64::MAX).unwrap_err();
+    assert!(matches!(err, Error::FeeTooHigh(u64::MAX, DEFAULT_MAX_FEE)));
+    assert_eq!(tx.auth.origin().fee(), DEFAULT_MAX_FEE);
+
+    assert!(tx.set_fee_capped(1_000, 500).is_err());
+    tx.set_fee_capped(500, 500).unwrap();
+    assert_eq!(tx.auth.origin().fee(), 500);
+}
+
+#[test]
+fn test_transaction_token_transfer_p2wpkh() {
+    let transfer = STXTokenTransfer {
+        fee: 180,
+        nonce: 1,
+        hash_mode: SingleSigMode::P2WPKH,
+        ..token_transfer(StacksMainnet::new())
+    };
+
+    let tx = transfer.transaction().sign(private_key()).unwrap();
+    let bytes = tx.encode().unwrap();
+
+    assert_eq!(tx.auth.origin().mode(), Mode::P2WPKH);
+    assert_eq!(bytes[6], 0x02);
+
+    let hash = SignatureHash::from_transaction(&tx).unwrap();
+    assert!(tx.auth.verify_origin(hash).is_ok());
+}
+
+#[test]
+fn test_transaction_token_transfer_runtime_network() {
+    let build = |network: StacksNetwork| {
+        STXTokenTransfer {
+            memo: "test memo".into(),
+            post_condition_mode: PostConditionMode::Deny,
+            ..token_transfer(network)
+        }
+        .transaction()
+        .sign(private_key())
+        .unwrap()
+    };
+
+    let mainnet = build(StacksNetwork::mainnet());
+    let testnet = build(StacksNetwork::testnet());
+
+    assert_eq!(
+        bytes_to_hex(mainnet.hash().unwrap().as_bytes()),
+        "84cccb05f4bd0e1b08905ef1f1350ad635a6474448310548bdccfa04e0121bab"
+    );
+    assert_eq!(
+        bytes_to_hex(testnet.hash().unwrap().as_bytes()),
+        "77c84320d3e7afe61b630d95a4548c45cbe00c270af1a0c8afda71efb9cf3499"
+    );
+}
+
+#[test]
+fn test_transaction_token_transfer_custom_chain_id() {
+    let network = StacksNetwork::custom(
+        0x5533_0000,
+        TransactionVersion::Testnet,
+        "http://127.0.0.1:30443",
+    );
+
+    let tx = token_transfer(network)
+        .transaction()
+        .sign(private_key())
+        .unwrap();
+
+    let encoded = tx.encode().unwrap();
+    assert_eq!(encoded[0], TransactionVersion::Testnet as u8);
+    assert_eq!(encoded[1..5], 0x5533_0000u32.to_be_bytes());
+
+    let hash = SignatureHash::from_transaction(&tx).unwrap();
+    assert!(tx.auth.verify_origin(hash).is_ok());
+}
+
+#[test]
+fn test_transaction_token_transfer_serialize_unsigned() {
+    let unsigned = STXTokenTransfer {
+        fee: 180,
+        nonce: 3,
+        ..token_transfer(StacksMainnet::new())
+    }
+    .transaction();
+
+    let presign = unsigned.encode().unwrap();
+    assert_eq!(unsigned.serialize_unsigned().unwrap(), presign);
+
+    let signed = unsigned.sign(private_key()).unwrap();
+    let bytes = signed.serialize_unsigned().unwrap();
+    assert_eq!(bytes, presign);
+    assert_eq!(bytes.len(), signed.len().unwrap());
+    assert_ne!(bytes, signed.encode().unwrap());
+
+    // The single-sig signature follows the key encoding byte at offset 43.
+    assert!(bytes[44..109].iter().all(|byte| *byte == 0));
+}
+
+#[test]
+fn test_transaction_token_transfer_sponsor_mismatch() {
+    let transfer = token_transfer(StacksMainnet::new());
+
+    let mut tx = transfer.transaction();
+    if let Auth::Standard(origin) = tx.auth {
+        tx.auth = Auth::Sponsored(origin, Box::<SpendingConditionStandard>::default());
+    }
+
+    let mut signer = TransactionSigner::new(tx).unwrap();
+    signer.sign_origin(private_key()).unwrap();
+    let tx = signer.transaction();
+
+    let sponsor_key = SecretKey::from_slice(&[0x01; 32]).unwrap();
+    let other_key = SecretKey::from_slice(&[0x02; 32]).unwrap();
+    let sponsor_pk = sponsor_key.public_key(secp256k1_context());
+
+    let sponsor = SpendingConditionStandard::new(sponsor_pk, 180, 1, Mode::P2PKH);
+    let mut signer = TransactionSigner::new_sponser(&tx, Box::new(sponsor)).unwrap();
+    let err = signer.sign_sponsor(other_key).unwrap_err();
+    assert!(matches!(err, Error::BadSigner(_, _)));
+    assert_eq!(signer.tx.auth.sponsor().unwrap().signatures(), 0);
+
+    signer.sign_sponsor(sponsor_key).unwrap();
+    let signed = signer.transaction();
+    assert!(signed
+        .auth
+        .verify(SignatureHash::from_transaction(&signed).unwrap())
+        .is_ok());
+
+    let sponsor = SpendingConditionStandard::new(sponsor_pk, 180, 1, Mode::P2WPKH);
+    let mut signer = TransactionSigner::new_sponser(&tx, Box::new(sponsor)).unwrap();
+    assert!(signer.sign_sponsor(sponsor_key).is_ok());
+}
+
+#[test]
+fn test_transaction_token_transfer_responsor() {
+    let transfer = token_transfer(StacksMainnet::new());
+
+    let standard = transfer.transaction();
+    let mut tx = standard.clone();
+    if let Auth::Standard(origin) = tx.auth {
+        tx.auth = Auth::Sponsored(origin, Box::<SpendingConditionStandard>::default());
+    }
+
+    let mut signer = TransactionSigner::new(tx).unwrap();
+    signer.sign_origin(private_key()).unwrap();
+    let tx = signer.transaction();
+
+    let sponsor_key = SecretKey::from_slice(&[0x01; 32]).unwrap();
+    let sponsored = tx.responsor(sponsor_key, 180, 1, Mode::P2PKH).unwrap();
+    let bumped = sponsored
+        .responsor(sponsor_key, 360, 1, Mode::P2PKH)
+        .unwrap();
+
+    assert_ne!(sponsored.hash().unwrap(), bumped.hash().unwrap());
+    assert_eq!(bumped.auth.sponsor().unwrap().fee(), 360);
+    assert_eq!(bumped.auth.sponsor().unwrap().nonce(), 1);
+    assert_eq!(
+        sponsored.auth.origin().encode().unwrap(),
+        bumped.auth.origin().encode().unwrap()
+    );
+    let hash = SignatureHash::from_transaction(&bumped).unwrap();
+    assert!(bumped.auth.verify_origin(hash).is_ok());
+    assert!(bumped.auth.verify(hash).is_ok());
+
+    let err = tx.responsor(sponsor_key, 180, 1, Mode::P2SH).unwrap_err();
+    assert!(matches!(err, Error::BadSpendingConditionModification));
+
+    let standard = standard.sign(private_key()).unwrap();
+    let err = standard
+        .responsor(sponsor_key, 180, 1, Mode::P2PKH)
+        .unwrap_err();
+    assert!(matches!(err, Error::BadSpendingConditionModification));
+}
+
+#[test]
+fn test_transaction_token_transfer_validate_anchor_mode() {
+    let build = |anchor_mode| {
+        STXTokenTransfer {
+            anchor_mode,
+            ..token_transfer(StacksMainnet::new())
+        }
+        .transaction()
+    };
+
+    assert!(build(AnchorMode::Any).validate().is_ok());
+    assert!(build(AnchorMode::Strict).validate().is_ok());
+
+    let err = build(AnchorMode::Micro).validate().unwrap_err();
+    assert!(matches!(
+        err,
+        Error::DeprecatedAnchorMode(AnchorMode::Micro)
+    ));
+}
+
+#[test]
+fn test_transaction_token_transfer_replace_by_fee() {
+    let transfer = STXTokenTransfer {
+        fee: 180,
+        nonce: 7,
+        ..token_transfer(StacksMainnet::new())
+    };
+
+    let tx = transfer.transaction().sign(private_key()).unwrap();
+    let bumped = tx.with_fee(360);
+
+    assert_eq!(bumped.auth.origin().fee(), 360);
+    assert_eq!(bumped.auth.origin().nonce(), 7);
+    assert_eq!(bumped.auth.origin().signatures(), 0);
+
+    let bumped = bumped.sign(private_key()).unwrap();
+    assert_ne!(bumped.hash().unwrap(), tx.hash().unwrap());
+    assert_eq!(&bumped.encode().unwrap()[35..43], &360u64.to_be_bytes());
+    assert!(bumped
+        .auth
+        .verify_origin(SignatureHash::from_transaction(&bumped).unwrap())
+        .is_ok());
+
+    let renonced = tx.with_nonce(8);
+    assert_eq!(renonced.auth.origin().fee(), 180);
+    assert_eq!(renonced.auth.origin().nonce(), 8);
+}
+
+#[test]
+fn test_transaction_token_transfer_validate_post_condition_mode() {
+    let build = |post_condition_mode| {
+        STXTokenTransfer {
+            post_condition_mode,
+            post_conditions: post_conditions(),
+            ..token_transfer(StacksMainnet::new())
+        }
+        .transaction()
+    };
+
+    assert!(build(PostConditionMode::Deny).validate().is_ok());
+
+    let err = build(PostConditionMode::Allow).validate().unwrap_err();
+    assert!(matches!(err, Error::AllowModeWithConditions(n) if n > 0));
+
+    let mut tx = build(PostConditionMode::Allow);
+    tx.post_conditions = PostConditions::default();
+    assert!(tx.validate().is_ok());
+}
+
+#[test]
+fn test_transaction_token_transfer_to_json() {
+    let transaction = STXTokenTransfer {
+        fee: 180,
+        nonce: 3,
+        memo: "test memo".into(),
+        post_condition_mode: PostConditionMode::Deny,
+        post_conditions: post_conditions(),
+        ..token_transfer(StacksMainnet::new())
+    }
+    .transaction();
+
+    let json = transaction.to_json();
+
+    assert_eq!(json["version"], "Mainnet");
+    assert_eq!(json["chain_id"], 1);
+    assert_eq!(json["anchor_mode"], "Any");
+    assert_eq!(json["post_condition_mode"], "Deny");
+
+    assert_eq!(json["auth"]["type"], "standard");
+    assert_eq!(json["auth"]["origin"]["hash_mode"], "p2pkh");
+    assert_eq!(
+        json["auth"]["origin"]["signer"],
+        "15c31b8c1c11c515e244b75806bac48d1399c775"
+    );
+    assert_eq!(json["auth"]["origin"]["nonce"], 3);
+    assert_eq!(json["auth"]["origin"]["fee"], 180);
+
+    assert_eq!(json["payload"]["type"], "token_transfer");
+    assert_eq!(
+        json["payload"]["recipient"],
+        "SP3FGQ8Z7JY9BWYZ5WM53E0M9NK7WHJF0691NZ159"
+    );
+    assert_eq!(json["payload"]["amount"], 12345);
+    assert_eq!(json["payload"]["memo"], "test memo");
+
+    let conditions = json["post_conditions"].as_array().unwrap();
+    assert_eq!(conditions.len(), 4);
+    assert_eq!(conditions[0]["type"], "stx");
+    assert_eq!(conditions[0]["code"], "GTE");
+    assert_eq!(conditions[0]["amount"], 1_000_000);
+    assert_eq!(conditions[2]["type"], "non_fungible");
+    assert_eq!(conditions[2]["name"], "u60149");
+    assert_eq!(
+        conditions[3]["asset"],
+        "SP2JXKMSH007NPYAQHKJPQMAQYAD90NQGTVJVQ02B.my-contract::my-asset"
+    );
+}
+
+#[test]
+fn test_transaction_mode_defaults() {
+    #[derive(Default)]
+    struct Options {
+        anchor_mode: AnchorMode,
+        post_condition_mode: PostConditionMode,
+    }
+
+    let options = Options::default();
+    assert_eq!(options.anchor_mode, AnchorMode::Any);
+    assert_eq!(options.post_condition_mode, PostConditionMode::Deny);
+}
+
+#[test]
+fn test_transaction_token_transfer_sign_twice() {
+    let tx = STXTokenTransfer {
+        fee: 100_000,
+        nonce: 55,
+        ..token_transfer(StacksMainnet::new())
+    }
+    .transaction();
+
+    let signed = tx.sign(private_key()).unwrap();
+    assert_eq!(signed.auth.origin().signatures(), 1);
+
+    let err = signed.clone().sign(private_key()).unwrap_err();
+    assert!(matches!(err, Error::OriginOversign));
+
+    let mut signer = TransactionSigner::new(signed).unwrap();
+    assert!(matches!(
+        signer.sign_origin(private_key()),
+        Err(Error::OriginOversign)
+    ));
+
+    // Without the check, a single-sig origin's signature is replaced.
+    signer.verify_oversign = false;
+    signer.sign_origin(private_key()).unwrap();
+    assert_eq!(signer.transaction().auth.origin().signatures(), 1);
+}
+
+#[test]
+fn test_transaction_token_transfer_total_cost() {
+    let transfer = |amount, fee| STXTokenTransfer {
+        amount,
+        fee,
+        ..token_transfer(StacksMainnet::new())
+    };
+
+    assert_eq!(transfer(1_000_000, 180).total_cost().unwrap(), 1_000_180);
+    assert_eq!(transfer(0, 0).total_cost().unwrap(), 0);
+    assert!(matches!(
+        transfer(u64::MAX, 1).total_cost(),
+        Err(Error::CostOverflow(u64::MAX, 1))
+    ));
+}
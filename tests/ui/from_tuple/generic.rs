@@ -0,0 +1,67 @@
+// © 2024 Max Karou. All Rights Reserved.
+// Licensed under Apache Version 2.0, or MIT License, at your discretion.
+//
+// Apache License: http://www.apache.org/licenses/LICENSE-2.0
+// MIT License: http://opensource.org/licenses/MIT
+//
+// Usage of this file is permitted solely under a sanctioned license.
+
+use std::marker::PhantomData;
+
+use stacks_rs::clarity;
+use stacks_rs::clarity::Tuple;
+use stacks_rs::derive;
+use stacks_rs::derive::FromTuple;
+
+#[derive(FromTuple)]
+struct Data<T: Into<i128>> {
+    #[stacks(key = "a")]
+    a: i128,
+    marker: PhantomData<T>,
+}
+
+#[derive(FromTuple)]
+struct Wrapper<T: TryFrom<Tuple, Error = derive::Error>> {
+    #[stacks(key = "inner")]
+    inner: T,
+    #[stacks(key = "maybe")]
+    maybe: Option<T>,
+}
+
+mod nested {
+    use stacks_rs::derive::FromTuple;
+
+    #[derive(FromTuple)]
+    pub struct Owner {
+        #[stacks(key = "id")]
+        pub id: u128,
+    }
+}
+
+#[derive(FromTuple)]
+struct Qualified {
+    #[stacks(key = "owner")]
+    owner: nested::Owner,
+}
+
+fn main() {
+    let data = Data::<i64>::try_from(clarity!(Tuple, ("a", clarity!(Int, 1)))).unwrap();
+    assert_eq!(data.a, 1);
+    let _ = data.marker;
+
+    let wrapper = Wrapper::<Data<i32>>::try_from(clarity!(
+        Tuple,
+        ("inner", clarity!(Tuple, ("a", clarity!(Int, 2)))),
+        ("maybe", clarity!(OptionalNone))
+    ))
+    .unwrap();
+    assert_eq!(wrapper.inner.a, 2);
+    assert!(wrapper.maybe.is_none());
+
+    let qualified = Qualified::try_from(clarity!(
+        Tuple,
+        ("owner", clarity!(Tuple, ("id", clarity!(UInt, 3))))
+    ))
+    .unwrap();
+    assert_eq!(qualified.owner.id, 3);
+}
@@ -0,0 +1,51 @@
+// © 2024 Max Karou. All Rights Reserved.
+// Licensed under Apache Version 2.0, or MIT License, at your discretion.
+//
+// Apache License: http://www.apache.org/licenses/LICENSE-2.0
+// MIT License: http://opensource.org/licenses/MIT
+//
+// Usage of this file is permitted solely under a sanctioned license.
+
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use stacks_rs::clarity;
+use stacks_rs::clarity::Tuple;
+use stacks_rs::derive;
+use stacks_rs::derive::FromTuple;
+
+#[derive(FromTuple)]
+struct Data<T>
+where
+    T: Into<i128> + Debug,
+{
+    #[stacks(key = "a")]
+    a: u128,
+    marker: PhantomData<T>,
+}
+
+#[derive(FromTuple)]
+struct Wrapper<'a, T>
+where
+    T: TryFrom<Tuple, Error = derive::Error> + 'a,
+{
+    #[stacks(key = "inner")]
+    inner: Option<T>,
+    marker: PhantomData<&'a T>,
+}
+
+fn main() {
+    let data = Data::<u8>::try_from(clarity!(Tuple, ("a", clarity!(UInt, 1)))).unwrap();
+    assert_eq!(data.a, 1);
+
+    let wrapper = Wrapper::<Data<u8>>::try_from(clarity!(
+        Tuple,
+        (
+            "inner",
+            clarity!(OptionalSome, clarity!(Tuple, ("a", clarity!(UInt, 2))))
+        )
+    ))
+    .unwrap();
+    assert_eq!(wrapper.inner.map(|inner| inner.a), Some(2));
+    let _ = wrapper.marker;
+}